@@ -0,0 +1,53 @@
+//! Generates the solution registry used by the `all` command.
+//!
+//! Every scaffolded `src/bin/YYYY-DD.rs` is mounted as a module of the main binary, so all
+//! solutions can be invoked in-process instead of spawning one `cargo run` per day.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut puzzles: Vec<((u16, u8), String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+                    let (year, day) = path.file_stem()?.to_str()?.split_once('-')?;
+                    if year.len() != 4 || day.len() != 2 {
+                        return None;
+                    }
+                    let year = year.parse::<u16>().ok().filter(|y| *y >= 2015)?;
+                    let day = day.parse::<u8>().ok().filter(|d| (1..=25).contains(d))?;
+                    Some(((year, day), path.to_str()?.to_owned()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    puzzles.sort_unstable();
+
+    let mut out = String::new();
+
+    for ((year, day), path) in &puzzles {
+        out.push_str(&format!("#[path = {path:?}]\nmod day_{year}_{day:02};\n"));
+    }
+
+    out.push_str(
+        "\n/// Every solution that has been scaffolded in `src/bin`, ordered by year and day.\n",
+    );
+    out.push_str("pub const SOLUTIONS: &[advent_of_code::template::Solution] = &[\n");
+    for ((year, day), _) in &puzzles {
+        out.push_str(&format!("    day_{year}_{day:02}::SOLUTION,\n"));
+    }
+    out.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
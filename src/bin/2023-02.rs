@@ -1,13 +1,14 @@
 use std::cmp::max;
 
 use nom::bytes::complete::tag;
-use nom::character::complete::{alpha0, char, space1, u32};
+use nom::character::complete::{alpha0, char, newline, space1, u32};
 use nom::combinator::map;
 use nom::multi::separated_list1;
 use nom::sequence::{pair, preceded, tuple};
 use nom::IResult;
 
-advent_of_code::solution!(2);
+use advent_of_code::template::{parse_input, SolveError};
+advent_of_code::solution!(2023, 2);
 
 struct Hand {
     blue: u32,
@@ -45,11 +46,12 @@ fn parse_game(i: &str) -> IResult<&str, Vec<Hand>> {
     )(i)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games = input
-        .lines()
-        .map(|l| parse_game(l).unwrap().1)
-        .collect::<Vec<_>>();
+fn parse_games(i: &str) -> IResult<&str, Vec<Vec<Hand>>> {
+    separated_list1(newline, parse_game)(i)
+}
+
+pub fn part_one(input: &str) -> Result<u32, SolveError> {
+    let games = parse_input(input, parse_games)?;
 
     let limit = Hand {
         blue: 14,
@@ -67,14 +69,11 @@ pub fn part_one(input: &str) -> Option<u32> {
         })
         .map(|h| (h.0 + 1) as u32)
         .sum::<u32>();
-    Some(sum)
+    Ok(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let games = input
-        .lines()
-        .map(|l| parse_game(l).unwrap().1)
-        .collect::<Vec<_>>();
+pub fn part_two(input: &str) -> Result<u32, SolveError> {
+    let games = parse_input(input, parse_games)?;
 
     let total_power = games
         .into_iter()
@@ -90,7 +89,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             max.blue * max.red * max.green
         })
         .sum::<u32>();
-    Some(total_power)
+    Ok(total_power)
 }
 
 #[cfg(test)]
@@ -99,13 +98,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(2286));
     }
 }
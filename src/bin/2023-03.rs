@@ -0,0 +1,101 @@
+use std::collections::{HashMap, HashSet};
+
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::template::SolveError;
+advent_of_code::solution!(2023, 3);
+
+/// A number in the schematic, along with the points of its digits.
+struct Number {
+    value: u32,
+    points: Vec<Point>,
+}
+
+fn parse_schematic(input: &str) -> Result<(Grid<char>, Vec<Number>), SolveError> {
+    let grid: Grid<char> = input.parse().map_err(|e| format!("{e}"))?;
+    let mut numbers = vec![];
+
+    for (y, row) in grid.rows().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let len = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if len > 0 {
+                let digits: String = row[x..x + len].iter().collect();
+                numbers.push(Number {
+                    value: digits
+                        .parse()
+                        .map_err(|_| format!("number {digits} is too large"))?,
+                    points: (x..x + len).map(|x| Point::new(x, y)).collect(),
+                });
+            }
+            x += len.max(1);
+        }
+    }
+
+    Ok((grid, numbers))
+}
+
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+pub fn part_one(input: &str) -> Result<u32, SolveError> {
+    let (grid, numbers) = parse_schematic(input)?;
+
+    let sum = numbers
+        .iter()
+        .filter(|number| {
+            number
+                .points
+                .iter()
+                .flat_map(|&point| grid.neighbours_8(point))
+                .any(|point| is_symbol(grid[point]))
+        })
+        .map(|number| number.value)
+        .sum();
+
+    Ok(sum)
+}
+
+pub fn part_two(input: &str) -> Result<u32, SolveError> {
+    let (grid, numbers) = parse_schematic(input)?;
+
+    let number_at: HashMap<Point, usize> = numbers
+        .iter()
+        .enumerate()
+        .flat_map(|(id, number)| number.points.iter().map(move |&point| (point, id)))
+        .collect();
+
+    let sum = grid
+        .positions(|&c| c == '*')
+        .map(|gear| {
+            let adjacent: HashSet<usize> = grid
+                .neighbours_8(gear)
+                .filter_map(|point| number_at.get(&point).copied())
+                .collect();
+
+            match adjacent.into_iter().collect::<Vec<_>>()[..] {
+                [a, b] => numbers[a].value * numbers[b].value,
+                _ => 0,
+            }
+        })
+        .sum();
+
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(4361));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(467835));
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use advent_of_code::template::{parse_input, SolveError};
 use nom::character::complete::{newline, space1, u8};
 use nom::sequence::{preceded, tuple};
 use nom::IResult;
@@ -7,9 +8,11 @@ use nom::{
     bytes::complete::tag, character::complete::digit1, combinator::map, multi::separated_list1,
     sequence::separated_pair,
 };
-advent_of_code::solution!(4);
+advent_of_code::solution!(2023, 4);
 
-pub fn parse_cards(input: &str) -> IResult<&str, Vec<(HashSet<u8>, Vec<u8>)>> {
+type Card = (HashSet<u8>, Vec<u8>);
+
+pub fn parse_cards(input: &str) -> IResult<&str, Vec<Card>> {
     separated_list1(
         newline,
         preceded(
@@ -23,30 +26,28 @@ pub fn parse_cards(input: &str) -> IResult<&str, Vec<(HashSet<u8>, Vec<u8>)>> {
     )(input)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games: Vec<(HashSet<u8>, Vec<u8>)> = parse_cards(input).unwrap().1;
-    Some(
-        games
-            .into_iter()
-            .map(|(winners, cards)| {
-                cards.into_iter().fold(0, |acc, card| {
-                    if winners.contains(&card) {
-                        if acc == 0 {
-                            1
-                        } else {
-                            acc + acc
-                        }
+pub fn part_one(input: &str) -> Result<u32, SolveError> {
+    let games: Vec<Card> = parse_input(input, parse_cards)?;
+    Ok(games
+        .into_iter()
+        .map(|(winners, cards)| {
+            cards.into_iter().fold(0, |acc, card| {
+                if winners.contains(&card) {
+                    if acc == 0 {
+                        1
                     } else {
-                        acc
+                        acc + acc
                     }
-                })
+                } else {
+                    acc
+                }
             })
-            .sum::<u32>(),
-    )
+        })
+        .sum::<u32>())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let cards: Vec<(HashSet<u8>, Vec<u8>)> = parse_cards(input).unwrap().1;
+pub fn part_two(input: &str) -> Result<u32, SolveError> {
+    let cards: Vec<Card> = parse_input(input, parse_cards)?;
 
     let mut cards_on_hand = (0..cards.len()).collect::<Vec<usize>>();
     let mut i = 0_usize;
@@ -62,7 +63,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
         i += 1;
     }
-    Some(cards_on_hand.len() as u32)
+    Ok(cards_on_hand.len() as u32)
 }
 
 #[cfg(test)]
@@ -71,13 +72,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(30));
     }
 }
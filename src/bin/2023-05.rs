@@ -0,0 +1,149 @@
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::{newline, space0, space1, u64},
+    combinator::{map, map_opt},
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded, tuple},
+    IResult,
+};
+
+use advent_of_code::ranges::{RangeMap, RangeSet};
+use advent_of_code::template::{parse_input, SolveError};
+advent_of_code::solution!(2023, 5, parse = parse_almanac);
+
+// seeds: 79 14 55 13
+
+// seed-to-soil map:
+// 50 98 2
+// 52 50 48
+
+// soil-to-fertilizer map:
+// 0 15 37
+
+pub struct Almanac {
+    seeds: Vec<u64>,
+    /// The seeds read as pairs of start and length, as in part two.
+    seed_ranges: RangeSet<u64>,
+    maps: Vec<RangeMap>,
+}
+
+fn parse(i: &str) -> IResult<&str, (Vec<u64>, Vec<RangeMap>)> {
+    pair(
+        delimited(tag("seeds: "), separated_list1(space1, u64), tag("\n\n")),
+        separated_list1(
+            tag("\n\n"),
+            preceded(
+                pair(take_until("\n"), newline),
+                map(
+                    separated_list1(
+                        newline,
+                        map_opt(
+                            tuple((
+                                preceded(space0, u64),
+                                preceded(space0, u64),
+                                preceded(space0, u64),
+                            )),
+                            // both ranges must stay within u64, so mapping a value can't overflow.
+                            |(dest, src, n)| {
+                                dest.checked_add(n)?;
+                                Some((src..src.checked_add(n)?, dest))
+                            },
+                        ),
+                    ),
+                    RangeMap::from_iter,
+                ),
+            ),
+        ),
+    )(i)
+}
+
+pub fn parse_almanac(input: &str) -> Result<Almanac, SolveError> {
+    let (seeds, maps) = parse_input(input, parse)?;
+
+    if seeds.len() % 2 != 0 {
+        return Err("the seeds don't form pairs of start and length".into());
+    }
+
+    let seed_ranges = seeds
+        .chunks_exact(2)
+        .map(|pair| {
+            let end = pair[0]
+                .checked_add(pair[1])
+                .ok_or("a seed range is too long")?;
+            Ok(pair[0]..end)
+        })
+        .collect::<Result<_, SolveError>>()?;
+
+    Ok(Almanac {
+        seeds,
+        seed_ranges,
+        maps,
+    })
+}
+
+pub fn part_one(almanac: &Almanac) -> Result<u64, SolveError> {
+    let min_location = almanac
+        .seeds
+        .iter()
+        .map(|&seed| almanac.maps.iter().fold(seed, |pos, map| map.map(pos)))
+        .min();
+
+    min_location.ok_or_else(|| "no seeds".into())
+}
+
+pub fn part_two(almanac: &Almanac) -> Result<u64, SolveError> {
+    let locations = almanac
+        .maps
+        .iter()
+        .fold(almanac.seed_ranges.clone(), |positions, map| {
+            map.map_set(&positions)
+        });
+
+    locations.min().ok_or_else(|| "no seeds".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = parse_almanac(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(input.and_then(|input| part_one(&input)), Ok(35));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = parse_almanac(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(input.and_then(|input| part_two(&input)), Ok(46));
+    }
+
+    #[test]
+    fn test_invalid_seeds() {
+        let example = advent_of_code::template::read_file("examples", PUZZLE);
+        let odd = example.replacen("seeds: 79 14", "seeds: 79", 1);
+        let too_long = example.replacen("seeds: 79 14", "seeds: 79 18446744073709551615", 1);
+
+        assert!(matches!(parse_almanac(&odd), Err(SolveError::Invalid(_))));
+        assert!(matches!(
+            parse_almanac(&too_long),
+            Err(SolveError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_maps() {
+        let example = advent_of_code::template::read_file("examples", PUZZLE);
+        let source = example.replacen("50 98 2", "50 18446744073709551615 2", 1);
+        let destination = example.replacen("50 98 2", "18446744073709551615 98 2", 1);
+
+        assert!(matches!(
+            parse_almanac(&source),
+            Err(SolveError::Parse { .. })
+        ));
+        assert!(matches!(
+            parse_almanac(&destination),
+            Err(SolveError::Parse { .. })
+        ));
+    }
+}
@@ -0,0 +1,150 @@
+use advent_of_code::math::isqrt;
+use advent_of_code::template::{parse_input, SolveError};
+use nom::bytes::complete::tag;
+use nom::character::complete::{digit1, newline, space1, u64};
+use nom::combinator::{map, map_res};
+use nom::multi::separated_list1;
+use nom::sequence::{pair, preceded, separated_pair};
+use nom::IResult;
+advent_of_code::solution!(2023, 6);
+
+// -- EXAMPLE --
+// Time:      7  15   30
+// Distance:  9  40  200
+
+fn parse(i: &str) -> IResult<&str, Vec<(u64, u64)>> {
+    map(
+        separated_pair(
+            preceded(pair(tag("Time:"), space1), separated_list1(space1, u64)),
+            newline,
+            preceded(
+                pair(tag("Distance: "), space1),
+                separated_list1(space1, u64),
+            ),
+        ),
+        |(a, b)| a.into_iter().zip(b).collect(),
+    )(i)
+}
+
+/// The number of hold times that beat the record distance.
+///
+/// Holding for `t` of `time` milliseconds travels `t * (time - t)`, so the winning hold times lie
+/// strictly between the roots of `t² - time * t + distance`. They are symmetric around `time / 2`,
+/// so the count follows from the smallest one.
+fn ways_to_win(time: u64, distance: u64) -> u64 {
+    let (time, distance) = (u128::from(time), u128::from(distance));
+    let wins = |t: u128| t * (time - t) > distance;
+
+    let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+        return 0;
+    };
+
+    // the integer root is at most 1 below the real root, so the smallest winning hold time is at
+    // most 2 above this estimate of the lower root.
+    let mut t = (time - isqrt(discriminant)) / 2;
+    while t <= time / 2 && !wins(t) {
+        t += 1;
+    }
+
+    if t > time / 2 {
+        return 0;
+    }
+
+    // the hold times `t..=time - t` win. Holding for 0 never wins, so `t >= 1` and the count is
+    // below `time`, which fits into an u64.
+    u64::try_from(time - 2 * t + 1).unwrap()
+}
+
+pub fn part_one(input: &str) -> Result<u64, SolveError> {
+    let races = parse_input(input, parse)?;
+    Ok(races
+        .iter()
+        .map(|&(time, distance)| ways_to_win(time, distance))
+        .product())
+}
+
+/// Parses the numbers of a line as a single number, ignoring the spaces between them.
+fn kerned_number(i: &str) -> IResult<&str, u64> {
+    map_res(separated_list1(space1, digit1), |digits: Vec<&str>| {
+        digits.concat().parse::<u64>()
+    })(i)
+}
+
+fn parse_2(i: &str) -> IResult<&str, (u64, u64)> {
+    separated_pair(
+        preceded(pair(tag("Time:"), space1), kerned_number),
+        newline,
+        preceded(pair(tag("Distance: "), space1), kerned_number),
+    )(i)
+}
+
+pub fn part_two(input: &str) -> Result<u64, SolveError> {
+    let (time, distance) = parse_input(input, parse_2)?;
+
+    Ok(ways_to_win(time, distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(288));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(71503));
+    }
+
+    #[test]
+    fn test_part_two_rejects_overflow() {
+        let result = part_two("Time: 7 15 30\nDistance: 9 40 200 18446744073709551615\n");
+        assert!(matches!(result, Err(SolveError::Parse { line: 2, .. })));
+    }
+
+    fn ways_to_win_brute_force(time: u64, distance: u64) -> u64 {
+        (0..=time).filter(|t| t * (time - t) > distance).count() as u64
+    }
+
+    #[test]
+    fn test_ways_to_win_matches_brute_force() {
+        // xorshift, so the races are random but the same on every run.
+        let mut state = 0x2023_0006_u64;
+        let mut random = |max: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % (max + 1)
+        };
+
+        for _ in 0..10_000 {
+            let time = random(200);
+            // mostly records that can be beaten, some that can't.
+            let distance = random(time * time / 4 + 10);
+            assert_eq!(
+                ways_to_win(time, distance),
+                ways_to_win_brute_force(time, distance),
+                "time {time}, distance {distance}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ways_to_win_at_boundaries() {
+        for time in 0..100_u64 {
+            // the record equals the best distance, a perfect square or one below it.
+            let best = time / 2 * (time - time / 2);
+            for distance in best.saturating_sub(2)..=best + 1 {
+                assert_eq!(
+                    ways_to_win(time, distance),
+                    ways_to_win_brute_force(time, distance),
+                    "time {time}, distance {distance}"
+                );
+            }
+        }
+    }
+}
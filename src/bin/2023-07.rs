@@ -0,0 +1,180 @@
+use std::marker::PhantomData;
+
+use nom::{
+    bytes::complete::take,
+    character::complete::{newline, space1, u32},
+    combinator::map_opt,
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult,
+};
+
+use advent_of_code::template::{parse_input, SolveError};
+advent_of_code::solution!(2023, 7);
+
+// example:
+// 32T3K 765
+// T55J5 684
+// KK677 28
+// KTJJT 220
+// QQQJA 483
+
+/// How cards rank against each other, and which card is wild. Rules are marker types, the bounds
+/// let hands derive their traits.
+trait Rules: Copy + Ord {
+    /// The cards from weakest to strongest.
+    const ORDER: &'static str;
+    /// A card that counts as whichever card makes the strongest hand.
+    const WILDCARD: Option<char> = None;
+}
+
+/// The rules of part one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Standard;
+
+impl Rules for Standard {
+    const ORDER: &'static str = "23456789TJQKA";
+}
+
+/// The rules of part two: jokers are the weakest card but wild.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Jokers;
+
+impl Rules for Jokers {
+    const ORDER: &'static str = "J23456789TQKA";
+    const WILDCARD: Option<char> = Some('J');
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    /// The type of a hand, given how often its most and second most common cards occur.
+    fn from_counts(first: usize, second: usize) -> Self {
+        match (first, second) {
+            (5, _) => Self::FiveOfAKind,
+            (4, _) => Self::FourOfAKind,
+            (3, 2) => Self::FullHouse,
+            (3, _) => Self::ThreeOfAKind,
+            (2, 2) => Self::TwoPair,
+            (2, _) => Self::OnePair,
+            _ => Self::HighCard,
+        }
+    }
+}
+
+/// A hand under the given rules. Hands order by their type first and then by their cards in the
+/// order they were dealt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Hand<R> {
+    hand_type: HandType,
+    ranks: [u8; 5],
+    rules: PhantomData<R>,
+}
+
+impl<R: Rules> Hand<R> {
+    /// Creates a hand, returns [`None`] if it holds a card that is not part of the rules.
+    fn new(cards: &str) -> Option<Self> {
+        let ranks: Vec<u8> = cards
+            .chars()
+            .map(|card| R::ORDER.find(card).map(|rank| rank as u8))
+            .collect::<Option<_>>()?;
+
+        let mut counts = [0; 13];
+        let mut wildcards = 0;
+        for card in cards.chars() {
+            if Some(card) == R::WILDCARD {
+                wildcards += 1;
+            } else {
+                counts[R::ORDER.find(card)?] += 1;
+            }
+        }
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        // wildcards always make the most common card more common.
+        Some(Self {
+            hand_type: HandType::from_counts(counts[0] + wildcards, counts[1]),
+            ranks: ranks.try_into().ok()?,
+            rules: PhantomData,
+        })
+    }
+}
+
+fn parse<R: Rules>(i: &str) -> IResult<&str, Vec<(Hand<R>, u32)>> {
+    separated_list1(
+        newline,
+        separated_pair(map_opt(take(5_usize), Hand::new), space1, u32),
+    )(i)
+}
+
+/// The sum of each hand's bid multiplied by its rank among all hands.
+fn total_winnings<R: Rules>(input: &str) -> Result<u32, SolveError> {
+    let mut hands = parse_input(input, parse::<R>)?;
+
+    hands.sort_unstable_by_key(|(hand, _)| *hand);
+
+    Ok(hands
+        .iter()
+        .zip(1..)
+        .map(|((_, bid), rank)| bid * rank)
+        .sum())
+}
+
+pub fn part_one(input: &str) -> Result<u32, SolveError> {
+    total_winnings::<Standard>(input)
+}
+
+pub fn part_two(input: &str) -> Result<u32, SolveError> {
+    total_winnings::<Jokers>(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(6440));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(5905));
+    }
+
+    #[test]
+    fn test_hand_types() {
+        let hand_type = |cards| Hand::<Standard>::new(cards).unwrap().hand_type;
+        assert_eq!(hand_type("32T3K"), HandType::OnePair);
+        assert_eq!(hand_type("KK677"), HandType::TwoPair);
+        assert_eq!(hand_type("T55J5"), HandType::ThreeOfAKind);
+        assert_eq!(hand_type("23332"), HandType::FullHouse);
+        assert_eq!(hand_type("JJJJJ"), HandType::FiveOfAKind);
+
+        let hand_type = |cards| Hand::<Jokers>::new(cards).unwrap().hand_type;
+        assert_eq!(hand_type("T55J5"), HandType::FourOfAKind);
+        assert_eq!(hand_type("2345J"), HandType::OnePair);
+        assert_eq!(hand_type("2233J"), HandType::FullHouse);
+        assert_eq!(hand_type("JJJJJ"), HandType::FiveOfAKind);
+
+        assert!(Hand::<Standard>::new("2345X").is_none());
+    }
+
+    #[test]
+    fn test_jokers_are_weakest() {
+        // hands of the same type are ordered by their cards.
+        assert!(Hand::<Jokers>::new("JKKK2") < Hand::<Jokers>::new("QQQQ2"));
+        assert!(Hand::<Jokers>::new("JJJJJ") < Hand::<Jokers>::new("22222"));
+        assert!(Hand::<Standard>::new("JJJJJ") > Hand::<Standard>::new("22222"));
+    }
+}
@@ -0,0 +1,189 @@
+use std::{collections::HashMap, iter};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, alphanumeric1, newline},
+    combinator::map,
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair},
+    IResult,
+};
+
+use advent_of_code::math::{brent, crt, Cycle};
+use advent_of_code::template::{parse_input, SolveError};
+advent_of_code::solution!(2023, 8);
+
+type Network = HashMap<String, (String, String)>;
+
+fn parse(i: &str) -> IResult<&str, (String, Network)> {
+    separated_pair(
+        map(alpha1, |s: &str| s.to_owned()),
+        pair(newline, newline),
+        map(
+            separated_list1(
+                newline,
+                separated_pair(
+                    map(alphanumeric1, |s: &str| s.to_owned()),
+                    tag(" = "),
+                    delimited(
+                        tag("("),
+                        separated_pair(
+                            map(alphanumeric1, |s: &str| s.to_owned()),
+                            tag(", "),
+                            map(alphanumeric1, |s: &str| s.to_owned()),
+                        ),
+                        tag(")"),
+                    ),
+                ),
+            ),
+            |v| v.into_iter().collect(),
+        ),
+    )(i)
+}
+
+/// Parses the path and the network, checking that the path only turns left or right.
+fn parse_network(input: &str) -> Result<(Vec<char>, Network), SolveError> {
+    let (path, map) = parse_input(input, parse)?;
+    let path: Vec<char> = path.chars().collect();
+
+    if let Some(c) = path.iter().find(|c| !matches!(c, 'L' | 'R')) {
+        return Err(format!("unexpected direction `{c}`").into());
+    }
+
+    Ok((path, map))
+}
+
+pub fn part_one(input: &str) -> Result<u32, SolveError> {
+    let (path, map) = parse_network(input)?;
+    let mut current = "AAA";
+
+    // a state is a node and the position in the path, once every state could have been visited
+    // without reaching `ZZZ`, the walk is cycling without it.
+    let states = map.len() * path.len();
+    for (i, c) in path.iter().cycle().enumerate().take(states + 1) {
+        if current == "ZZZ" {
+            return Ok(i as u32);
+        }
+        let (left, right) = map
+            .get(current)
+            .ok_or_else(|| format!("unknown node {current}"))?;
+        current = if *c == 'L' { left } else { right };
+    }
+
+    Err("`ZZZ` can't be reached from `AAA`".into())
+}
+
+/// The cycle a walker ends up in, and the steps within its first pass after which it is on an end
+/// node.
+struct Walker {
+    cycle: Cycle,
+    ends: Vec<usize>,
+}
+
+impl Walker {
+    /// Whether the walker is on an end node after `steps` steps.
+    fn is_end(&self, steps: usize) -> bool {
+        let Cycle { start, length } = self.cycle;
+        let steps = if steps < start {
+            steps
+        } else {
+            start + (steps - start) % length
+        };
+        self.ends.binary_search(&steps).is_ok()
+    }
+}
+
+pub fn part_two(input: &str) -> Result<u64, SolveError> {
+    let (path, map) = parse_network(input)?;
+
+    let mut walkers = vec![];
+    for start in map.keys().filter(|k| k.ends_with('A')) {
+        // a state is a node and the position in the path, so it repeats once the walker cycles.
+        let states = iter::successors(Some((start.as_str(), 0)), |&(node, i)| {
+            let (left, right) = map.get(node)?;
+            let next = if path[i] == 'L' { left } else { right };
+            Some((next.as_str(), (i + 1) % path.len()))
+        });
+
+        let cycle = brent(states.clone()).ok_or("a walker leaves the network")?;
+        let ends = states
+            .take(cycle.start + cycle.length)
+            .enumerate()
+            .filter(|(_, (node, _))| node.ends_with('Z'))
+            .map(|(steps, _)| steps)
+            .collect();
+
+        walkers.push(Walker { cycle, ends });
+    }
+
+    // before every walker reached its cycle, the steps are checked one by one.
+    let all_cycling = walkers.iter().map(|w| w.cycle.start).max().unwrap_or(0);
+    if let Some(steps) = (0..all_cycling).find(|&steps| walkers.iter().all(|w| w.is_end(steps))) {
+        return Ok(steps as u64);
+    }
+
+    // afterwards, each walker is on an end node at one of its ends within the cycle, plus any
+    // multiple of the cycle length. Every combination of these ends is a system of congruences.
+    let systems = walkers.iter().fold(vec![vec![]], |systems, walker| {
+        let Cycle { start, length } = walker.cycle;
+        let residues = walker
+            .ends
+            .iter()
+            .filter(|&&steps| steps >= start)
+            .map(|&steps| ((steps % length) as u64, length as u64));
+
+        residues
+            .flat_map(|residue| {
+                systems.iter().map(move |system: &Vec<(u64, u64)>| {
+                    let mut system = system.clone();
+                    system.push(residue);
+                    system
+                })
+            })
+            .collect()
+    });
+
+    systems
+        .into_iter()
+        .filter_map(crt)
+        .map(|(steps, length)| {
+            // the smallest solution at which all walkers are cycling.
+            let all_cycling = all_cycling as u64;
+            steps + all_cycling.saturating_sub(steps).div_ceil(length) * length
+        })
+        .min()
+        .ok_or_else(|| "the walkers never end at the same time".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 1,
+        ));
+        assert_eq!(result, Ok(6));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 2,
+        ));
+        assert_eq!(result, Ok(6));
+    }
+
+    #[test]
+    fn test_part_one_rejects_malformed_networks() {
+        let invalid = |input: &str| matches!(part_one(input), Err(SolveError::Invalid(_)));
+
+        assert!(invalid("LX\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)"));
+        assert!(invalid("L\n\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)"));
+        assert!(invalid("L\n\nAAA = (BBB, BBB)\nZZZ = (ZZZ, ZZZ)"));
+        assert!(invalid(
+            "LR\n\nAAA = (BBB, AAA)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, ZZZ)"
+        ));
+    }
+}
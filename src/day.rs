@@ -114,6 +114,142 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days of advent, e.g. to run a subset of all solutions.
+///
+/// # Parsing
+/// A set is parsed from a comma-separated list of days and ranges. Ranges include both ends,
+/// and either end can be omitted.
+///
+/// ```
+/// # use advent_of_code::DaySet;
+/// let days: DaySet = "1-5,8,12-".parse().unwrap();
+/// assert_eq!(days.len(), 20);
+/// assert_eq!(days.to_string(), "1-5,8,12-25");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// The set of all days of advent.
+    #[must_use]
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    #[must_use]
+    pub fn contains(self, day: Day) -> bool {
+        self.0 & (1 << day.0) != 0
+    }
+
+    pub fn insert(&mut self, day: Day) {
+        self.0 |= 1 << day.0;
+    }
+
+    #[must_use]
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates the days of the set in ascending order.
+    pub fn iter(self) -> impl Iterator<Item = Day> {
+        all_days().filter(move |day| self.contains(*day))
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        Self(1 << day.0)
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        let mut set = Self(0);
+        iter.into_iter().for_each(|day| set.insert(day));
+        set
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ranges: Vec<(u8, u8)> = vec![];
+
+        for day in self.iter() {
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == day.0 => *end = day.0,
+                _ => ranges.push((day.0, day.0)),
+            }
+        }
+
+        let ranges: Vec<String> = ranges
+            .into_iter()
+            .map(|(start, end)| match end - start {
+                0 => start.to_string(),
+                _ => format!("{start}-{end}"),
+            })
+            .collect();
+
+        f.write_str(&ranges.join(","))
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |s: &str| s.trim().parse::<Day>().map_err(|_| DaySetFromStrError);
+        let mut set = Self(0);
+
+        for item in s.split(',') {
+            let (start, end) = match item.split_once('-') {
+                None => {
+                    let day = parse_day(item)?;
+                    (day, day)
+                }
+                Some((start, end)) => (
+                    match start.trim() {
+                        "" => Day(1),
+                        start => parse_day(start)?,
+                    },
+                    match end.trim() {
+                        "" => Day(25),
+                        end => parse_day(end)?,
+                    },
+                ),
+            };
+
+            if start > end {
+                return Err(DaySetFromStrError);
+            }
+
+            all_days()
+                .filter(|day| (start..=end).contains(day))
+                .for_each(|day| set.insert(day));
+        }
+
+        Ok(set)
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError;
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting days between 1 and 25 like `8` or `1-5,8,12-`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,7 +270,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +303,32 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_sets() {
+        let days = |s: &str| {
+            s.parse::<DaySet>()
+                .map(|set| set.iter().map(Day::into_inner).collect::<Vec<_>>())
+                .ok()
+        };
+
+        assert_eq!(days("8"), Some(vec![8]));
+        assert_eq!(days("1-3, 8,24-"), Some(vec![1, 2, 3, 8, 24, 25]));
+        assert_eq!(days("-2,2"), Some(vec![1, 2]));
+        assert_eq!(days("-").map(|days| days.len()), Some(25));
+        assert_eq!(days(""), None);
+        assert_eq!(days("0"), None);
+        assert_eq!(days("5-3"), None);
+        assert_eq!(days("1,,2"), None);
+        assert_eq!(days("1-26"), None);
+    }
+
+    #[test]
+    fn displays_day_sets() {
+        assert_eq!(DaySet::all().to_string(), "1-25");
+        assert_eq!(DaySet::from(Day(8)).to_string(), "8");
+        assert_eq!("3,1,2,5".parse::<DaySet>().unwrap().to_string(), "1-3,5");
+    }
 }
 
 /* -------------------------------------------------------------------------- */
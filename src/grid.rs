@@ -0,0 +1,501 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// A position in a [`Grid`], `x` being the column and `y` the row, starting at the top left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Moves the point by the given offset, returns [`None`] if it would leave the first quadrant.
+    #[must_use]
+    pub fn offset(self, (dx, dy): (isize, isize)) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// The offsets of the four orthogonal neighbours: up, right, down and left.
+pub const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The offsets of all eight neighbours, clockwise starting at the top left.
+pub const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// # Parsing
+/// Grids parse from lines of equal length, converting every character into a cell, e.g.
+/// `"#.\n.#"` into a 2x2 grid with `'#'` at `(0, 0)` and `(1, 1)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given size, with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from its cells in row-major order, returns [`None`] if they don't fill
+    /// whole rows of the given width.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Parses a grid, converting every character into a cell with `parse`.
+    pub fn parse_with(
+        s: &str,
+        parse: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, GridFromStrError> {
+        let mut cells = vec![];
+        let mut width = None;
+
+        for (y, line) in s.lines().enumerate() {
+            let len = cells.len();
+
+            for (x, c) in line.chars().enumerate() {
+                let cell = parse(c).ok_or(GridFromStrError::InvalidCell { x, y, value: c })?;
+                cells.push(cell);
+            }
+
+            match width {
+                None => width = Some(cells.len() - len),
+                Some(width) if width != cells.len() - len => {
+                    return Err(GridFromStrError::RaggedLine { y });
+                }
+                Some(_) => {}
+            }
+        }
+
+        let width = width
+            .filter(|width| *width > 0)
+            .ok_or(GridFromStrError::Empty)?;
+        Ok(Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the point lies inside the grid.
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.y * self.width + point.x])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.contains(point)
+            .then(|| &mut self.cells[point.y * self.width + point.x])
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::new(i % width, i / width))
+    }
+
+    /// All cells along with their points, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The points of the orthogonal neighbours of a point that lie inside the grid.
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.offsets(point, &NEIGHBOURS_4)
+    }
+
+    /// The points of all neighbours of a point, including diagonal ones, that lie inside the grid.
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.offsets(point, &NEIGHBOURS_8)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        point: Point,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Point> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&offset| point.offset(offset))
+            .filter(|point| self.contains(*point))
+    }
+
+    /// The points from `start` in the direction of `step` up to the edge of the grid, including
+    /// `start` itself.
+    pub fn walk(&self, start: Point, step: (isize, isize)) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(Some(start), move |point| point.offset(step))
+            .take_while(|point| self.contains(*point))
+    }
+
+    /// The cells of a row, panics if it is out of bounds.
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // not `chunks`, which panics for grids without columns.
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// The cells of a column from top to bottom, panics if it is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The diagonals running from the top left to the bottom right, starting with the one in the
+    /// bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height)
+            .rev()
+            .map(|y| Point::new(0, y))
+            .chain((1..self.width).map(|x| Point::new(x, 0)))
+            // a grid without columns or rows has no diagonals.
+            .filter(|start| self.contains(*start));
+
+        starts.map(|start| self.walk(start, (1, 1)).map(|point| &self[point]))
+    }
+
+    /// The diagonals running from the top right to the bottom left, starting with the one in the
+    /// top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let last_column = self.width.checked_sub(1);
+        let starts = (0..self.width)
+            .map(|x| Point::new(x, 0))
+            .chain(
+                last_column
+                    .into_iter()
+                    .flat_map(|x| (1..self.height).map(move |y| Point::new(x, y))),
+            )
+            .filter(|start| self.contains(*start));
+
+        starts.map(|start| self.walk(start, (-1, 1)).map(|point| &self[point]))
+    }
+
+    /// The point of the first cell that satisfies the predicate, row by row.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.positions(predicate).next()
+    }
+
+    /// The points of all cells that satisfy the predicate, row by row.
+    pub fn positions<'a>(
+        &'a self,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// The point of the first cell that equals `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+
+    /// Converts every cell, keeping its point.
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along the diagonal from the top left to the bottom right, i.e. rows become
+    /// columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |x, y| Point::new(y, x))
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |x, y| {
+            Point::new(y, self.height - 1 - x)
+        })
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn rotate_counter_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |x, y| {
+            Point::new(self.width - 1 - y, x)
+        })
+    }
+
+    /// Builds a grid of the given size, taking each cell from the point `source` returns for it.
+    fn rebuild(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> Point) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[source(x, y)].clone())
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("point {point} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("point {point} is out of bounds"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = GridFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| T::try_from(c).ok())
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridFromStrError {
+    /// The input holds no cells.
+    Empty,
+    /// A line is longer or shorter than the first one.
+    RaggedLine { y: usize },
+    /// A character could not be converted into a cell.
+    InvalidCell { x: usize, y: usize, value: char },
+}
+
+impl Error for GridFromStrError {}
+
+impl Display for GridFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => f.write_str("expecting at least one cell"),
+            Self::RaggedLine { y } => {
+                write!(f, "expecting lines of equal length, line {} differs", y + 1)
+            }
+            Self::InvalidCell { x, y, value } => write!(
+                f,
+                "unexpected `{value}` at line {}, column {}",
+                y + 1,
+                x + 1
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridFromStrError, Point};
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits: Grid<u8> = Grid::parse_with("12\n34\n", |c| {
+            c.to_digit(10).and_then(|d| u8::try_from(d).ok())
+        })
+        .unwrap();
+        assert_eq!(digits.row(1), [3, 4]);
+    }
+
+    #[test]
+    fn handles_grids_without_cells() {
+        let no_columns = Grid::new(0, 2, '.');
+        assert_eq!(
+            no_columns.rows().map(<[char]>::len).collect::<Vec<_>>(),
+            [0, 0]
+        );
+        assert_eq!(no_columns.columns().count(), 0);
+        assert_eq!(no_columns.points().count(), 0);
+        assert_eq!(no_columns.diagonals().count(), 0);
+        assert_eq!(no_columns.anti_diagonals().count(), 0);
+
+        let no_rows = no_columns.transpose();
+        assert_eq!((no_rows.width(), no_rows.height()), (2, 0));
+        assert_eq!(no_rows.rows().count(), 0);
+        assert_eq!(collect(no_rows.columns()), ["", ""]);
+        assert_eq!(no_rows.diagonals().count(), 0);
+        assert_eq!(no_rows.anti_diagonals().count(), 0);
+    }
+
+    #[test]
+    fn rejects_invalid_grids() {
+        assert_eq!("".parse::<Grid<char>>(), Err(GridFromStrError::Empty));
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>(),
+            Err(GridFromStrError::RaggedLine { y: 1 })
+        );
+        assert_eq!(
+            Grid::parse_with("..\n.x", |c| (c == '.').then_some(())),
+            Err(GridFromStrError::InvalidCell {
+                x: 1,
+                y: 1,
+                value: 'x'
+            })
+        );
+        assert!(Grid::from_cells(2, vec![1, 2, 3]).is_none());
+    }
+
+    #[test]
+    fn finds_neighbours_in_bounds() {
+        let grid = grid();
+        let corner: Vec<Point> = grid.neighbours_8(Point::new(0, 0)).collect();
+        assert_eq!(
+            corner,
+            [Point::new(1, 0), Point::new(1, 1), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours_4(Point::new(1, 1)).count(), 3);
+        assert_eq!(grid.neighbours_8(Point::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn views_rows_columns_and_diagonals() {
+        let grid = grid();
+        assert_eq!(collect(grid.columns()), ["ad", "be", "cf"]);
+        assert_eq!(collect(grid.diagonals()), ["d", "ae", "bf", "c"]);
+        assert_eq!(collect(grid.anti_diagonals()), ["a", "bd", "ce", "f"]);
+        assert_eq!(
+            grid.walk(Point::new(0, 1), (1, 0)).collect::<Vec<_>>(),
+            [Point::new(0, 1), Point::new(1, 1), Point::new(2, 1)]
+        );
+    }
+
+    #[test]
+    fn finds_positions() {
+        let grid = grid();
+        assert_eq!(grid.find(&'e'), Some(Point::new(1, 1)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(
+            grid.positions(|c| "aeiou".contains(*c)).collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
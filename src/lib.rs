@@ -1,4 +1,9 @@
 mod day;
+pub mod grid;
+pub mod math;
+pub mod ranges;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use year::*;
@@ -1,30 +1,43 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, watch};
 use args::{parse, AppArguments};
 
+/// Every solution in `src/bin`, mounted as a module so `all` can run them in-process.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
+    use std::num::NonZeroUsize;
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::{template::runner::RunOptions, DaySet, PuzzleId, Year};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            year: Year,
+            days: DaySet,
+            force: bool,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            year: Year,
+            days: DaySet,
         },
         Solve {
-            day: Day,
+            year: Year,
+            days: DaySet,
             release: bool,
-            time: bool,
-            submit: Option<u8>,
+            watch: bool,
+            options: RunOptions,
         },
         All {
+            year: Year,
+            days: DaySet,
             release: bool,
-            time: bool,
+            jobs: Option<NonZeroUsize>,
+            options: RunOptions,
         },
     }
 
@@ -33,24 +46,39 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: parse_year(&mut args)?,
+                days: args
+                    .opt_value_from_str("--days")?
+                    .unwrap_or_else(DaySet::all),
                 release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                jobs: args.opt_value_from_str("--jobs")?,
+                options: RunOptions::parse(&mut args)?,
             },
+            Some("download") => {
+                let force = args.contains("--force");
+                let (year, days) = parse_days(&mut args)?;
+                AppArguments::Download { year, days, force }
+            }
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                puzzle: parse_puzzle(&mut args)?,
             },
+            Some("scaffold") => {
+                let (year, days) = parse_days(&mut args)?;
+                AppArguments::Scaffold { year, days }
+            }
+            Some("solve") => {
+                let (year, days) = parse_days(&mut args)?;
+                AppArguments::Solve {
+                    year,
+                    days,
+                    release: args.contains("--release"),
+                    watch: args.contains("--watch"),
+                    options: RunOptions {
+                        submit: args.opt_value_from_str("--submit")?,
+                        ..RunOptions::parse(&mut args)?
+                    },
+                }
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -68,6 +96,31 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Reads the year from `--year`, falling back to `AOC_YEAR`.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Year::from_env()
+                .ok_or_else(|| "no year given, pass `--year` or set AOC_YEAR.".into()),
+        }
+    }
+
+    /// Reads a day and the year it belongs to.
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        Ok(PuzzleId::new(year, args.free_from_str()?))
+    }
+
+    /// Reads a set of days, e.g. `1-5,8`, and the year they belong to.
+    fn parse_days(
+        args: &mut pico_args::Arguments,
+    ) -> Result<(Year, DaySet), Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        Ok((year, args.free_from_str()?))
+    }
 }
 
 fn main() {
@@ -77,16 +130,32 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                days,
+                release,
+                jobs,
+                options,
+            } => {
+                all::handle(solutions::SOLUTIONS, year, days, release, jobs, &options);
+            }
+            AppArguments::Download { year, days, force } => download::handle(year, days, force),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold { year, days } => scaffold::handle(year, days),
+            AppArguments::Solve {
+                year,
+                days,
+                release,
+                watch: true,
+                options,
+            } => watch::handle(year, days, release, &options),
             AppArguments::Solve {
-                day,
+                year,
+                days,
                 release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
+                watch: false,
+                options,
+            } => solve::handle(year, days, release, &options),
         },
     };
 }
@@ -0,0 +1,284 @@
+/// The greatest common divisor, `gcd(0, 0)` is `0`.
+#[must_use]
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, `lcm(0, n)` is `0`.
+///
+/// # Panics
+/// Panics in debug builds if the result overflows.
+#[must_use]
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    a / gcd(a, b) * b
+}
+
+/// The greatest common divisor of all values, `0` if there are none.
+pub fn gcd_all(values: impl IntoIterator<Item = u64>) -> u64 {
+    values.into_iter().fold(0, gcd)
+}
+
+/// The least common multiple of all values, `1` if there are none.
+pub fn lcm_all(values: impl IntoIterator<Item = u64>) -> u64 {
+    values.into_iter().fold(1, lcm)
+}
+
+/// The integer square root, i.e. the largest `r` with `r * r <= n`.
+///
+/// Computed with Newton's method on integers, so unlike `(n as f64).sqrt()` it is exact for every
+/// value.
+#[must_use]
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // start above the root, the iteration then decreases monotonically until it reaches it.
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1 << (bits / 2 + 1);
+
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// The extended Euclidean algorithm: returns `(g, x, y)` with `a * x + b * y = g`, where `g` is
+/// the non-negative greatest common divisor of `a` and `b`.
+#[must_use]
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese Remainder Theorem.
+///
+/// The moduli don't need to be coprime. Returns `(x, m)` such that the solutions are exactly the
+/// values `x + k * m`, with `0 <= x < m`, or [`None`] if the congruences contradict each other, a
+/// modulus is `0` or `m` does not fit into an [`u64`].
+///
+/// Any [`u64`] moduli are supported: intermediate values are bounded by `m` or by the square of a
+/// single modulus, so they never overflow 128 bits.
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u64, u64)> {
+    let (x, m) = congruences
+        .into_iter()
+        .try_fold((0, 1), |combined, (residue, modulus)| {
+            merge_congruences(combined, (i128::from(residue), i128::from(modulus)))
+        })?;
+
+    Some((u64::try_from(x).ok()?, u64::try_from(m).ok()?))
+}
+
+/// Combines `x ≡ a (mod m)` and `x ≡ b (mod n)` into a single congruence.
+fn merge_congruences((a, m): (i128, i128), (b, n): (i128, i128)) -> Option<(i128, i128)> {
+    if n == 0 {
+        return None;
+    }
+
+    let (g, p, _) = extended_gcd(m, n);
+    let diff = b - a;
+
+    if diff % g != 0 {
+        return None;
+    }
+
+    // checked before anything else, the other values are then bounded by it.
+    let lcm = (m / g)
+        .checked_mul(n)
+        .filter(|lcm| u64::try_from(*lcm).is_ok())?;
+
+    // `p` inverts `m / g` modulo `n / g`, so `k` steps of `m` from `a` reach `b`. Both factors are
+    // below `n / g`, their product fits into an u128 but not always into an i128.
+    let step = n / g;
+    let k = (diff / g).rem_euclid(step).unsigned_abs() * p.rem_euclid(step).unsigned_abs()
+        % step.unsigned_abs();
+    let x = (a + i128::try_from(k).ok()? * m).rem_euclid(lcm);
+
+    Some((x, lcm))
+}
+
+/// A cycle in a sequence of states: after `start` states, the sequence repeats every `length`
+/// states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Finds the cycle of a sequence of states with Brent's algorithm, returns [`None`] if the
+/// sequence ends before it repeats.
+///
+/// The sequence must be deterministic, i.e. each state must always be followed by the same state.
+/// Only a constant number of states is kept, the iterator is cloned to rewind it.
+pub fn brent<T: PartialEq>(states: impl Iterator<Item = T> + Clone) -> Option<Cycle> {
+    let mut hare = states.clone();
+    let mut tortoise = hare.next()?;
+    let mut power = 1;
+    let mut length = 1;
+
+    loop {
+        let state = hare.next()?;
+        if state == tortoise {
+            break;
+        }
+        if power == length {
+            tortoise = state;
+            power *= 2;
+            length = 0;
+        }
+        length += 1;
+    }
+
+    Some(Cycle {
+        start: find_cycle_start(states, length)?,
+        length,
+    })
+}
+
+/// Finds the cycle of a sequence of states with Floyd's algorithm, returns [`None`] if the
+/// sequence ends before it repeats.
+///
+/// Like [`brent`], but usually needs more steps. The sequence must be deterministic.
+pub fn floyd<T: PartialEq>(states: impl Iterator<Item = T> + Clone) -> Option<Cycle> {
+    // the tortoise is at state `i` and the hare at state `2i`. They meet once both are in the
+    // cycle and `i` is a multiple of its length.
+    let tortoise = states.clone().skip(1);
+    let hare = states.clone().skip(2).step_by(2);
+    let i = tortoise.zip(hare).position(|(t, h)| t == h)? + 1;
+
+    let meeting = states.clone().nth(i)?;
+    let length = states
+        .clone()
+        .skip(i + 1)
+        .position(|state| state == meeting)?
+        + 1;
+
+    Some(Cycle {
+        start: find_cycle_start(states, length)?,
+        length,
+    })
+}
+
+/// Finds the first state that repeats `length` states later, i.e. the start of the cycle.
+fn find_cycle_start<T: PartialEq>(
+    states: impl Iterator<Item = T> + Clone,
+    length: usize,
+) -> Option<usize> {
+    let ahead = states.clone().skip(length);
+    states.zip(ahead).position(|(state, later)| state == later)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::iter;
+
+    use super::{brent, crt, extended_gcd, floyd, gcd, gcd_all, isqrt, lcm, lcm_all, Cycle};
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(lcm_all([2, 3, 4, 5]), 60);
+        assert_eq!(lcm_all([]), 1);
+    }
+
+    #[test]
+    fn computes_integer_square_roots() {
+        for n in 0..10_000_u128 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+        }
+
+        // floats lose precision long before these values.
+        for r in [u128::from(u32::MAX), 3_037_000_499, u128::from(u64::MAX)] {
+            assert_eq!(isqrt(r * r), r);
+            assert_eq!(isqrt(r * r - 1), r - 1);
+            assert_eq!(isqrt(r * r + 2 * r), r);
+        }
+        assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+    }
+
+    #[test]
+    fn computes_bezout_coefficients() {
+        for (a, b) in [(240, 46), (-240, 46), (7, 0), (0, 0), (17, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(
+                g,
+                i128::from(gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64))
+            );
+        }
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli that are not coprime.
+        assert_eq!(crt([(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt([(0, 4), (1, 6)]), None);
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        // the largest primes below 2^32 and 2^64.
+        let (p, q, r) = (4_294_967_291, 4_294_967_279, 18_446_744_073_709_551_557);
+        let (x, m) = crt([(12_345, p), (q - 1, q)]).unwrap();
+        assert_eq!(m, p * q);
+        assert_eq!((x % p, x % q), (12_345, q - 1));
+        assert_eq!(crt([(r - 1, r), (r - 1, r)]), Some((r - 1, r)));
+        assert_eq!(crt([(1, r), (2, r - 2)]), None);
+        assert_eq!(crt([(1, p), (2, r)]), None);
+        // the cycles of 2023 day 8 all end on a multiple of their length.
+        assert_eq!(
+            crt([(0, 20_093), (0, 12_169), (0, 22_357)]),
+            Some((0, lcm_all([20_093, 12_169, 22_357])))
+        );
+    }
+
+    #[test]
+    fn finds_cycles() {
+        // 0, 1, 2, 3, 4, 5, 6, 7 and then 3, 4, 5, 6, 7 repeat.
+        let states = iter::successors(Some(0), |&n| Some(if n == 7 { 3 } else { n + 1 }));
+        let cycle = Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(brent(states.clone()), Some(cycle));
+        assert_eq!(floyd(states), Some(cycle));
+
+        let fixed_point = iter::repeat(1);
+        let cycle = Cycle {
+            start: 0,
+            length: 1,
+        };
+        assert_eq!(brent(fixed_point.clone()), Some(cycle));
+        assert_eq!(floyd(fixed_point), Some(cycle));
+
+        assert_eq!(brent(0..10), None);
+        assert_eq!(floyd(0..10), None);
+    }
+}
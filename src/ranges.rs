@@ -0,0 +1,336 @@
+use std::ops::Range;
+
+/// A set of values stored as sorted, disjoint half-open ranges.
+///
+/// Overlapping and adjacent ranges are merged on insertion, so every set has exactly one
+/// representation and sets can be compared with `==`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    /// Builds a set from ranges in any order, dropping empty ones and merging the rest.
+    fn normalized(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|range| range.start < range.end);
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        Self { ranges: merged }
+    }
+
+    /// The disjoint ranges of the set, in ascending order.
+    #[must_use]
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        // the first range ending after the value is the only one that might contain it.
+        let i = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges.get(i).is_some_and(|range| range.start <= value)
+    }
+
+    /// The smallest value of the set.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::normalized(ranges);
+    }
+
+    /// The values contained in either set.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalized(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    /// The values contained in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let (start, end) = (x.start.max(y.start), x.end.min(y.end));
+            if start < end {
+                ranges.push(start..end);
+            }
+            // the range ending first can't overlap any later range of the other set.
+            if x.end <= y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// The values contained in this set but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut others = other.ranges.iter().peekable();
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            while let Some(cut) = others.peek() {
+                if cut.end <= start {
+                    others.next();
+                    continue;
+                }
+                if cut.start >= range.end {
+                    break;
+                }
+                if cut.start > start {
+                    ranges.push(start..cut.start);
+                }
+                start = cut.end;
+                if cut.end >= range.end {
+                    break;
+                }
+                others.next();
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Splits the set into the values below `at` and the values from `at` onwards.
+    #[must_use]
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = vec![];
+        let mut above = vec![];
+
+        for range in &self.ranges {
+            if range.end <= at {
+                below.push(range.clone());
+            } else if range.start >= at {
+                above.push(range.clone());
+            } else {
+                below.push(range.start..at);
+                above.push(at..range.end);
+            }
+        }
+
+        (Self { ranges: below }, Self { ranges: above })
+    }
+}
+
+impl RangeSet<u64> {
+    /// The number of values in the set.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|range| range.end - range.start)
+            .sum()
+    }
+
+    /// Moves every value of the set by `delta`.
+    ///
+    /// # Panics
+    /// Panics if a value would leave the range of `u64`.
+    #[must_use]
+    pub fn shift(&self, delta: i64) -> Self {
+        let shift = |value: u64| {
+            value
+                .checked_add_signed(delta)
+                .expect("range shifted out of bounds")
+        };
+
+        Self {
+            ranges: self
+                .ranges
+                .iter()
+                .map(|range| shift(range.start)..shift(range.end))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::normalized(vec![range])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalized(iter.into_iter().collect())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A piecewise mapping of values, e.g. one layer of an almanac. Each entry moves a source range
+/// to a destination, values outside of every source range map to themselves.
+///
+/// Entries are checked in insertion order, so if sources overlap the first one wins.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RangeMap {
+    entries: Vec<(Range<u64>, u64)>,
+}
+
+impl RangeMap {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the values of `source` to the values starting at `destination`. The destination range
+    /// must fit into an [`u64`], otherwise mapping values overflows.
+    pub fn insert(&mut self, source: Range<u64>, destination: u64) {
+        self.entries.push((source, destination));
+    }
+
+    /// Maps a single value.
+    #[must_use]
+    pub fn map(&self, value: u64) -> u64 {
+        self.entries
+            .iter()
+            .find(|(source, _)| source.contains(&value))
+            .map_or(value, |(source, destination)| {
+                value - source.start + destination
+            })
+    }
+
+    /// Maps every value of a set at once, splitting its ranges where they cross source ranges.
+    #[must_use]
+    pub fn map_set(&self, set: &RangeSet<u64>) -> RangeSet<u64> {
+        let mut mapped = vec![];
+        let mut unmapped = set.clone();
+
+        for (source, destination) in &self.entries {
+            let source_set = RangeSet::from(source.clone());
+            let moved = |value: u64| value - source.start + destination;
+
+            mapped.extend(
+                unmapped
+                    .intersection(&source_set)
+                    .iter()
+                    .map(|range| moved(range.start)..moved(range.end)),
+            );
+            unmapped = unmapped.difference(&source_set);
+        }
+
+        mapped.extend(unmapped.ranges);
+        RangeSet::normalized(mapped)
+    }
+}
+
+impl FromIterator<(Range<u64>, u64)> for RangeMap {
+    fn from_iter<I: IntoIterator<Item = (Range<u64>, u64)>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{RangeMap, RangeSet};
+
+    fn set(ranges: &[std::ops::Range<u64>]) -> RangeSet<u64> {
+        ranges.iter().cloned().collect()
+    }
+
+    /// Checks a set against the values in `0..limit` that satisfy the predicate.
+    fn assert_values(set: &RangeSet<u64>, limit: u64, predicate: impl Fn(u64) -> bool) {
+        for value in 0..limit {
+            assert_eq!(set.contains(value), predicate(value), "value {value}");
+        }
+    }
+
+    #[test]
+    fn normalizes_ranges() {
+        assert_eq!(
+            set(&[5..8, 0..2, 2..3, 7..10, 4..4]).ranges(),
+            [0..3, 5..10]
+        );
+        assert!(set(&[3..3]).is_empty());
+
+        let mut s = set(&[0..2]);
+        s.insert(4..6);
+        s.insert(1..5);
+        assert_eq!(s.ranges(), [0..6]);
+        assert_eq!(s.count(), 6);
+        assert_eq!(s.min(), Some(0));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[0..5, 10..15, 20..25]);
+        let b = set(&[3..12, 14..21, 30..31]);
+        let in_a = |v: u64| a.contains(v);
+        let in_b = |v: u64| b.contains(v);
+
+        assert_values(&a.union(&b), 40, |v| in_a(v) || in_b(v));
+        assert_values(&a.intersection(&b), 40, |v| in_a(v) && in_b(v));
+        assert_values(&a.difference(&b), 40, |v| in_a(v) && !in_b(v));
+        assert_values(&b.difference(&a), 40, |v| in_b(v) && !in_a(v));
+        assert_eq!(a.difference(&a), RangeSet::new());
+        assert_eq!(a.intersection(&RangeSet::new()), RangeSet::new());
+    }
+
+    #[test]
+    fn splits_and_shifts_sets() {
+        let (below, above) = set(&[0..5, 10..15]).split_at(12);
+        assert_eq!(below.ranges(), [0..5, 10..12]);
+        assert_eq!(above.ranges(), [12..15]);
+
+        assert_eq!(set(&[10..15]).shift(-10).ranges(), [0..5]);
+        assert_eq!(set(&[10..15]).shift(5).ranges(), [15..20]);
+    }
+
+    #[test]
+    fn maps_ranges() {
+        // the seed-to-soil map of 2023 day 5.
+        let map: RangeMap = [(98..100, 50), (50..98, 52)].into_iter().collect();
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(99), 51);
+        assert_eq!(map.map(10), 10);
+
+        let seeds = set(&[40..60, 95..105]);
+        let mapped = map.map_set(&seeds);
+        assert_eq!(mapped.count(), seeds.count());
+        assert_values(&mapped, 120, |v| {
+            (40..60).chain(95..105).any(|seed| map.map(seed) == v)
+        });
+    }
+}
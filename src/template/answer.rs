@@ -0,0 +1,106 @@
+/// Module that defines the answer a solution part returns.
+///
+/// Every integer type, strings and multi-line grids convert into an [`Answer`], so the runner can
+/// print, verify and submit any result without a per-day signature.
+use std::fmt::Display;
+
+/// The answer to one part of a puzzle.
+///
+/// Integers are normalized, so answers compare equal regardless of the type they were computed in.
+///
+/// ```
+/// # use advent_of_code::template::Answer;
+/// assert_eq!(Answer::from(42_u8), Answer::from(42_i64));
+/// assert_eq!(Answer::grid(["#.", ".#"]).to_string(), "#.\n.#");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    /// An unsigned integer that is too large for [`Answer::Integer`].
+    Unsigned(u128),
+    /// A text answer, e.g. a code or the letters of a grid, might span multiple lines.
+    Text(String),
+}
+
+impl Answer {
+    /// Creates a multi-line answer from the rows of a grid.
+    pub fn grid<T: Display>(rows: impl IntoIterator<Item = T>) -> Self {
+        let rows: Vec<String> = rows.into_iter().map(|row| row.to_string()).collect();
+        Self::Text(rows.join("\n"))
+    }
+
+    /// Whether the answer spans multiple lines, i.e. has to be read by a human before submitting.
+    #[must_use]
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Self::Text(text) if text.contains('\n'))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Unsigned(value) => write!(f, "{value}"),
+            Self::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value).map_or(Self::Unsigned(value), Self::Integer)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn normalizes_integers() {
+        assert_eq!(Answer::from(7_u32), Answer::Integer(7));
+        assert_eq!(Answer::from(-7_i8), Answer::Integer(-7));
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(Answer::from(u128::MAX), Answer::Unsigned(u128::MAX));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn detects_multiline_answers() {
+        assert!(Answer::grid(["#..", ".#."]).is_multiline());
+        assert!(!Answer::from("ABCDEF").is_multiline());
+        assert!(!Answer::from(12).is_multiline());
+    }
+}
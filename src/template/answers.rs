@@ -0,0 +1,249 @@
+/// Module that stores known-correct answers and verifies results against them.
+/// Answers live in one small TOML file per day, e.g. `data/2023/answers/01.toml`:
+///
+/// ```toml
+/// part_one = "142"
+/// part_two = "281"
+/// ```
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::{fs, io};
+
+use crate::template::get_data_path;
+use crate::PuzzleId;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse answers: {e}"),
+            Error::IO(e) => write!(f, "could not access answers: {e}"),
+        }
+    }
+}
+
+/// The known-correct answers of a day.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+/// The result of checking an answer against the store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Correct,
+    Incorrect {
+        expected: String,
+    },
+    /// There is no known answer for this part yet.
+    Unknown,
+}
+
+impl Verification {
+    #[must_use]
+    pub fn is_incorrect(&self) -> bool {
+        matches!(self, Self::Incorrect { .. })
+    }
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "✔"),
+            Self::Incorrect { expected } if expected.contains('\n') => write!(f, "✘"),
+            Self::Incorrect { expected } => write!(f, "✘ (expected {expected})"),
+            Self::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> PathBuf {
+    get_data_path(puzzle, "answers", "toml")
+}
+
+impl Answers {
+    /// Loads the answers of a day, returns empty answers if none have been stored yet.
+    pub fn load(puzzle: PuzzleId) -> Result<Self, Error> {
+        match fs::read_to_string(get_path(puzzle)) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        let path = get_path(puzzle);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.serialize())?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_one = Some(answer.into()),
+            2 => self.part_two = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    /// Checks an answer against the known answer of a part.
+    #[must_use]
+    pub fn verify(&self, part: u8, answer: Option<&str>) -> Verification {
+        match (self.get(part), answer) {
+            (None, _) => Verification::Unknown,
+            (Some(expected), Some(answer)) if expected == answer => Verification::Correct,
+            (Some(expected), _) => Verification::Incorrect {
+                expected: expected.into(),
+            },
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self, Error> {
+        let mut answers = Self::default();
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(Error::Parser(format!("malformed line \"{line}\"")));
+            };
+
+            let value = parse_value(value.trim())
+                .ok_or_else(|| Error::Parser(format!("invalid value in \"{line}\"")))?;
+
+            match key.trim() {
+                "part_one" => answers.part_one = Some(value),
+                "part_two" => answers.part_two = Some(value),
+                key => return Err(Error::Parser(format!("unknown key \"{key}\""))),
+            }
+        }
+
+        Ok(answers)
+    }
+
+    fn serialize(&self) -> String {
+        let mut lines = vec![];
+        if let Some(answer) = &self.part_one {
+            lines.push(format!("part_one = {}", quote(answer)));
+        }
+        if let Some(answer) = &self.part_two {
+            lines.push(format!("part_two = {}", quote(answer)));
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Stores a correct answer, keeping the known answer of the other part.
+pub fn record(puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = Answers::load(puzzle)?;
+    answers.set(part, answer);
+    answers.save(puzzle)
+}
+
+/// Parses a TOML basic string or a bare integer.
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        let mut out = String::with_capacity(inner.len());
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => out.push(match chars.next()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    '"' => '"',
+                    '\\' => '\\',
+                    _ => return None,
+                }),
+                '"' => return None,
+                c => out.push(c),
+            }
+        }
+        Some(out)
+    } else {
+        // a bare integer, with at most one leading minus.
+        let digits = value.strip_prefix('-').unwrap_or(value);
+        (!digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())).then(|| value.into())
+    }
+}
+
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verification};
+
+    #[test]
+    fn parses_answers() {
+        let answers = Answers::parse("# day 1\npart_one = \"142\"\npart_two = 281\n").unwrap();
+        assert_eq!(answers.get(1), Some("142"));
+        assert_eq!(answers.get(2), Some("281"));
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        assert!(Answers::parse("part_one 142").is_err());
+        assert!(Answers::parse("part_three = 1").is_err());
+        assert!(Answers::parse("part_one = abc").is_err());
+        for value in ["-", "--5", "---", "5-"] {
+            assert!(
+                Answers::parse(&format!("part_one = {value}")).is_err(),
+                "{value}"
+            );
+        }
+        assert_eq!(Answers::parse("part_one = -5").unwrap().get(1), Some("-5"));
+    }
+
+    #[test]
+    fn round_trips_multiline_answers() {
+        let mut answers = Answers::default();
+        answers.set(1, "#.#\n\"x\"\\");
+        answers.set(2, "5905");
+        assert_eq!(Answers::parse(&answers.serialize()).unwrap(), answers);
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let answers = Answers {
+            part_one: Some("142".into()),
+            part_two: None,
+        };
+        assert_eq!(answers.verify(1, Some("142")), Verification::Correct);
+        assert!(answers.verify(1, Some("143")).is_incorrect());
+        assert!(answers.verify(1, None).is_incorrect());
+        assert_eq!(answers.verify(2, Some("1")), Verification::Unknown);
+    }
+}
@@ -0,0 +1,617 @@
+/// Native client for the Advent of Code website.
+///
+/// The session cookie is read from the `AOC_SESSION` environment variable or from an
+/// `.adventofcode.session` file in the home or config directory, the same file aoc-cli uses.
+/// The base URL can be overridden with `AOC_BASE_URL`, e.g. to point the client at a mock server.
+///
+/// Requests are spaced out by a minimum interval, and puzzles that have not been released yet are
+/// never requested. The time of the last request is stored in the data folder, so the interval
+/// also holds between clients and separate runs.
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs, io, thread};
+
+use crate::template::submissions::SubmissionOutcome;
+use crate::template::Answer;
+use crate::PuzzleId;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/peterall/aoc23";
+
+/// The minimum time between two requests.
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where the time of the last request is stored, in nanoseconds since the unix epoch.
+const LAST_REQUEST_PATH: &str = "data/.last_request";
+
+/// Puzzles are released at midnight UTC-5, i.e. at 05:00 UTC.
+const RELEASE_HOUR_UTC: u64 = 5;
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was configured.
+    MissingSession,
+    /// The session cookie was rejected or has expired.
+    Unauthorized,
+    /// The puzzle has not been released yet, with the time left until it is if known.
+    NotUnlocked {
+        remaining: Option<Duration>,
+    },
+    /// The server asked to wait before sending more requests.
+    RateLimited,
+    /// A multi-line answer, which has to be read by a human.
+    MultilineAnswer,
+    /// A response that could not be interpreted.
+    UnexpectedResponse(String),
+    /// Any other unexpected HTTP status.
+    BadStatus(u16),
+    Transport(String),
+    IoError(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or create a \".adventofcode.session\" file in your home directory."
+            ),
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie was rejected, it might have expired.")
+            }
+            AocClientError::NotUnlocked {
+                remaining: Some(remaining),
+            } => write!(
+                f,
+                "the puzzle has not been unlocked yet, it unlocks in {}.",
+                format_remaining(*remaining)
+            ),
+            AocClientError::NotUnlocked { remaining: None } => {
+                write!(f, "the puzzle has not been unlocked yet.")
+            }
+            AocClientError::RateLimited => {
+                write!(f, "rate limited, wait a bit before trying again.")
+            }
+            AocClientError::MultilineAnswer => write!(
+                f,
+                "multi-line answers have to be read and submitted by hand."
+            ),
+            AocClientError::UnexpectedResponse(message) => {
+                write!(f, "unexpected response: {message}")
+            }
+            AocClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "could not reach the server: {e}"),
+            AocClientError::IoError(e) => write!(f, "could not write output files: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IoError(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(400 | 401 | 403, _) => AocClientError::Unauthorized,
+            ureq::Error::Status(404, _) => AocClientError::NotUnlocked { remaining: None },
+            ureq::Error::Status(429, _) => AocClientError::RateLimited,
+            // the server answers requests with a malformed session cookie with an internal error.
+            ureq::Error::Status(500, response) => match response.into_string() {
+                Ok(body) if body.contains("session") => AocClientError::Unauthorized,
+                _ => AocClientError::BadStatus(500),
+            },
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Option<String>,
+    min_interval: Duration,
+    last_request_path: PathBuf,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: Option<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            min_interval: MIN_REQUEST_INTERVAL,
+            last_request_path: PathBuf::from(LAST_REQUEST_PATH),
+        }
+    }
+
+    /// Overrides the minimum time between two requests.
+    #[must_use]
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Overrides the file the time of the last request is stored in.
+    #[must_use]
+    pub fn with_last_request_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.last_request_path = path.into();
+        self
+    }
+
+    /// Creates a client configured through the environment. A missing session is only reported
+    /// once a request needs it, puzzle descriptions can be read without one.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Self::new(&base_url, read_session())
+    }
+
+    /// Fetches the input of a puzzle. Requires a session.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(puzzle));
+        let session = self.session()?;
+        self.prepare_request(puzzle)?;
+        Ok(self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={session}"))
+            .call()?
+            .into_string()?)
+    }
+
+    /// Fetches the description of a puzzle as the HTML of its `<article>` elements.
+    /// Part two is only included if a session is present and part one has been solved.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.prepare_request(puzzle)?;
+        let mut request = self.agent.get(&self.day_url(puzzle));
+        if let Some(session) = &self.session {
+            request = request.set("Cookie", &format!("session={session}"));
+        }
+        let html = request.call()?.into_string()?;
+        Ok(extract_articles(&html).join("\n\n"))
+    }
+
+    /// Submits an answer for one part of a puzzle, returns the classified response and its text.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &Answer,
+    ) -> Result<(SubmissionOutcome, String), AocClientError> {
+        if answer.is_multiline() {
+            return Err(AocClientError::MultilineAnswer);
+        }
+
+        let url = format!("{}/answer", self.day_url(puzzle));
+        let session = self.session()?;
+        self.prepare_request(puzzle)?;
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={session}"))
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ])?
+            .into_string()?;
+
+        let message = extract_articles(&html)
+            .first()
+            .map_or_else(|| html_to_text(&html), |article| html_to_text(article));
+
+        match SubmissionOutcome::parse(&message) {
+            Some(outcome) => Ok((outcome, message)),
+            None => Err(AocClientError::UnexpectedResponse(message)),
+        }
+    }
+
+    fn day_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    /// Refuses days that are still locked, and waits until the minimum interval has passed.
+    fn prepare_request(&self, puzzle: PuzzleId) -> Result<(), AocClientError> {
+        let remaining = unlocks_in(puzzle, SystemTime::now());
+        if !remaining.is_zero() {
+            return Err(AocClientError::NotUnlocked {
+                remaining: Some(remaining),
+            });
+        }
+
+        self.throttle();
+        Ok(())
+    }
+
+    /// Waits until the minimum interval has passed since the last request of any client. This is
+    /// best effort: a missing or unreadable file means no wait, and a failed write is ignored.
+    fn throttle(&self) {
+        if let Some(last_request) = read_last_request(&self.last_request_path) {
+            // a request in the future means the clock was turned back, wait the whole interval.
+            let elapsed = SystemTime::now()
+                .duration_since(last_request)
+                .unwrap_or_default();
+            if let Some(wait) = self.min_interval.checked_sub(elapsed) {
+                thread::sleep(wait);
+            }
+        }
+        let _ = write_last_request(&self.last_request_path, SystemTime::now());
+    }
+
+    fn session(&self) -> Result<&str, AocClientError> {
+        self.session
+            .as_deref()
+            .ok_or(AocClientError::MissingSession)
+    }
+}
+
+fn read_last_request(path: &Path) -> Option<SystemTime> {
+    let nanos = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    UNIX_EPOCH.checked_add(Duration::from_nanos(nanos))
+}
+
+fn write_last_request(path: &Path, time: SystemTime) -> io::Result<()> {
+    let nanos = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, nanos.to_string())
+}
+
+/// The moment a puzzle is released.
+#[must_use]
+pub fn unlock_time(puzzle: PuzzleId) -> SystemTime {
+    let days = days_from_civil(
+        puzzle.year.into_inner().into(),
+        12,
+        puzzle.day.into_inner().into(),
+    );
+    UNIX_EPOCH + Duration::from_secs(days * 86400 + RELEASE_HOUR_UTC * 3600)
+}
+
+/// The time left until a puzzle is released, zero if it already is.
+#[must_use]
+pub fn unlocks_in(puzzle: PuzzleId, now: SystemTime) -> Duration {
+    unlock_time(puzzle).duration_since(now).unwrap_or_default()
+}
+
+/// Days since the unix epoch of a date in the proleptic Gregorian calendar, for dates after 1970.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    match (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60) {
+        (0, 0, 0, s) => format!("{s}s"),
+        (0, 0, m, s) => format!("{m}m {s}s"),
+        (0, h, m, _) => format!("{h}h {m}m"),
+        (d, h, _, _) => format!("{d}d {h}h"),
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session.trim().to_string());
+        }
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let home = PathBuf::from(home);
+    let config = env::var_os("XDG_CONFIG_HOME").map_or_else(|| home.join(".config"), PathBuf::from);
+
+    [
+        home.join(".adventofcode.session"),
+        config.join("adventofcode.session"),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|session| session.trim().to_string())
+    .filter(|session| !session.is_empty())
+}
+
+/// Extracts the `<article>` elements holding the puzzle description or a submission response.
+#[must_use]
+pub fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(len) = rest[start..].find("</article>") else {
+            break;
+        };
+        let end = start + len + "</article>".len();
+        articles.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    articles
+}
+
+/// Renders HTML as plain text, keeping line breaks of block elements.
+#[must_use]
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[start + 1..start + len];
+        match tag.split_whitespace().next().unwrap_or_default() {
+            "/p" | "/h2" | "/pre" | "/li" | "br" | "br/" => text.push('\n'),
+            "li" => text.push_str("- "),
+            _ => {}
+        }
+        rest = &rest[start + len + 1..];
+    }
+    text.push_str(rest);
+
+    decode_entities(&text).trim().to_string()
+}
+
+/// Decodes the HTML entities that show up in puzzle descriptions.
+#[must_use]
+pub fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::env;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant, UNIX_EPOCH};
+
+    use super::{
+        extract_articles, html_to_text, unlock_time, unlocks_in, AocClient, AocClientError,
+    };
+    use crate::puzzle;
+    use crate::template::submissions::SubmissionOutcome;
+    use crate::template::Answer;
+
+    /// Serves a single canned response and returns the raw request it received.
+    fn mock_server(status: u16, body: &str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let body = body.to_string();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (url, rx)
+    }
+
+    /// A file for the time of the last request that no other test uses.
+    fn last_request_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("aoc-last-request-{}-{name}", std::process::id()))
+    }
+
+    fn client(url: &str) -> AocClient {
+        AocClient::new(url, Some("cookie".into()))
+            .with_min_interval(Duration::ZERO)
+            .with_last_request_path(last_request_path("unthrottled"))
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (url, rx) = mock_server(200, "1abc2\n");
+        assert_eq!(client(&url).input(puzzle!(2023, 1)).unwrap(), "1abc2\n");
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input "));
+        assert!(request.contains("session=cookie"));
+    }
+
+    #[test]
+    fn requires_session_for_input() {
+        let client = AocClient::new("http://localhost:1", None);
+        assert!(matches!(
+            client.input(puzzle!(2023, 1)),
+            Err(AocClientError::MissingSession)
+        ));
+    }
+
+    #[test]
+    fn maps_status_codes() {
+        let (url, _rx) = mock_server(404, "Please don't repeatedly request this endpoint.");
+        assert!(matches!(
+            client(&url).input(puzzle!(2023, 25)),
+            Err(AocClientError::NotUnlocked { remaining: None })
+        ));
+
+        let (url, _rx) = mock_server(400, "Puzzle inputs differ by user. Please log in.");
+        assert!(matches!(
+            client(&url).input(puzzle!(2023, 1)),
+            Err(AocClientError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn downloads_puzzle_articles() {
+        let (url, _rx) = mock_server(
+            200,
+            "<html><main><article class=\"day-desc\"><h2>--- Day 1 ---</h2></article><p>stars</p><article class=\"day-desc\"><p>two</p></article></main></html>",
+        );
+        assert_eq!(
+            client(&url).puzzle(puzzle!(2023, 1)).unwrap(),
+            "<article class=\"day-desc\"><h2>--- Day 1 ---</h2></article>\n\n<article class=\"day-desc\"><p>two</p></article>"
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, rx) = mock_server(
+            200,
+            "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+        );
+        let (outcome, message) = client(&url)
+            .submit(puzzle!(2023, 7), 2, &Answer::from(5905))
+            .unwrap();
+        assert_eq!(outcome, SubmissionOutcome::Correct);
+        assert!(message.starts_with("That's the right answer!"));
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/7/answer "));
+        assert!(request.ends_with("level=2&answer=5905"));
+    }
+
+    #[test]
+    fn classifies_wrong_answers() {
+        let (url, _rx) = mock_server(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        );
+        assert_eq!(
+            client(&url)
+                .submit(puzzle!(2023, 7), 1, &Answer::from(1))
+                .unwrap()
+                .0,
+            SubmissionOutcome::TooHigh
+        );
+
+        let (url, _rx) = mock_server(200, "<main><article><p>Unexpected.</p></article></main>");
+        assert!(matches!(
+            client(&url).submit(puzzle!(2023, 7), 1, &Answer::from(1)),
+            Err(AocClientError::UnexpectedResponse(_))
+        ));
+    }
+
+    #[test]
+    fn refuses_multiline_answers() {
+        let client = AocClient::new("http://localhost:1", Some("cookie".into()));
+        assert!(matches!(
+            client.submit(puzzle!(2023, 7), 1, &Answer::grid(["#.", ".#"])),
+            Err(AocClientError::MultilineAnswer)
+        ));
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(
+            unlock_time(puzzle!(2023, 1)),
+            UNIX_EPOCH + Duration::from_secs(1_701_406_800)
+        );
+        // 2024-12-25T05:00:00Z
+        assert_eq!(
+            unlock_time(puzzle!(2024, 25)),
+            UNIX_EPOCH + Duration::from_secs(1_735_102_800)
+        );
+
+        let release = unlock_time(puzzle!(2023, 1));
+        assert_eq!(
+            unlocks_in(puzzle!(2023, 1), release - Duration::from_secs(90)),
+            Duration::from_secs(90)
+        );
+        assert_eq!(unlocks_in(puzzle!(2023, 1), release), Duration::ZERO);
+    }
+
+    #[test]
+    fn refuses_locked_days() {
+        let client = AocClient::new("http://localhost:1", Some("cookie".into()));
+        assert!(matches!(
+            client.input(puzzle!(9999, 1)),
+            Err(AocClientError::NotUnlocked { remaining: Some(_) })
+        ));
+    }
+
+    #[test]
+    fn spaces_out_requests() {
+        let path = last_request_path("spaced");
+        let client = || {
+            AocClient::new("http://localhost:1", None)
+                .with_min_interval(Duration::from_millis(50))
+                .with_last_request_path(&path)
+        };
+
+        let start = Instant::now();
+        client().throttle();
+        client().throttle();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn extracts_articles() {
+        assert_eq!(extract_articles("<p>no articles</p>").len(), 0);
+        assert_eq!(
+            extract_articles("<article>a</article>x<article>b</article>"),
+            ["<article>a</article>", "<article>b</article>"]
+        );
+    }
+
+    #[test]
+    fn renders_html_as_text() {
+        assert_eq!(
+            html_to_text(
+                "<h2>--- Day 1 ---</h2><p>A &lt;b&gt; &amp; <em>c</em></p><ul><li>x</li></ul>"
+            ),
+            "--- Day 1 ---\nA <b> & c\n- x"
+        );
+    }
+}
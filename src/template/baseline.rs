@@ -0,0 +1,322 @@
+/// Module that stores benchmark timings as named baselines and compares runs against them.
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, io};
+
+use crate::template::runner::PartResult;
+use crate::template::{get_year_path, ANSI_BOLD, ANSI_RESET};
+use crate::{Day, Year};
+
+/// Changes smaller than this are considered noise, even if they are significant.
+const NOISE_THRESHOLD: f64 = 0.02;
+
+/// Two-tailed critical value of the normal distribution at a 95% confidence level.
+const CRITICAL_VALUE: f64 = 1.96;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    InvalidName(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse baseline: {e}"),
+            Error::InvalidName(name) => write!(f, "invalid baseline name \"{name}\""),
+            Error::IO(e) => write!(f, "could not access baseline: {e}"),
+        }
+    }
+}
+
+/// The timing of a benched part as stored in a baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Entry {
+    pub mean: Duration,
+    pub std_dev: Duration,
+    pub samples: u128,
+}
+
+/// A named set of timings of one year, keyed on day and part.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    entries: BTreeMap<(Day, u8), Entry>,
+}
+
+/// How a part performed compared to its baseline entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Improved,
+    Regressed,
+    NoChange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    /// The relative change of the mean, e.g. `0.1` for a 10% slowdown.
+    pub change: f64,
+    pub verdict: Verdict,
+}
+
+/// Whether a baseline can be stored under this name. Names become file names, so only letters,
+/// digits, `_` and `-` are allowed, which also keeps them from leaving the baselines directory.
+#[must_use]
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Parses the name passed to `--baseline` or `--save-baseline`.
+pub fn parse_name(s: &str) -> Result<String, String> {
+    if is_valid_name(s) {
+        Ok(s.to_string())
+    } else {
+        Err(format!(
+            "invalid baseline name `{s}`, expecting only letters, digits, `_` and `-`"
+        ))
+    }
+}
+
+#[must_use]
+pub fn get_path(year: Year, name: &str) -> PathBuf {
+    get_year_path(year)
+        .join("baselines")
+        .join(format!("{name}.tsv"))
+}
+
+impl Baseline {
+    /// Loads a baseline, returns an empty baseline if it has not been saved yet.
+    pub fn load(year: Year, name: &str) -> Result<Self, Error> {
+        if !is_valid_name(name) {
+            return Err(Error::InvalidName(name.to_string()));
+        }
+
+        match fs::read_to_string(get_path(year, name)) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the baseline, replacing any previous file of the same name.
+    pub fn save(&self, year: Year, name: &str) -> Result<(), Error> {
+        if !is_valid_name(name) {
+            return Err(Error::InvalidName(name.to_string()));
+        }
+
+        let path = get_path(year, name);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.serialize())?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, day: Day, part: u8) -> Option<&Entry> {
+        self.entries.get(&(day, part))
+    }
+
+    /// Stores the timing of a benched result, overwriting a previous entry for the same part.
+    pub fn record(&mut self, result: &PartResult) {
+        if let Some(stats) = result.stats {
+            self.entries.insert(
+                (result.puzzle.day, result.part),
+                Entry {
+                    mean: stats.mean,
+                    std_dev: stats.std_dev,
+                    samples: result.samples,
+                },
+            );
+        }
+    }
+
+    /// Compares a benched result against its entry in this baseline.
+    #[must_use]
+    pub fn compare(&self, result: &PartResult) -> Option<Comparison> {
+        let stats = result.stats?;
+        let current = Entry {
+            mean: stats.mean,
+            std_dev: stats.std_dev,
+            samples: result.samples,
+        };
+        Some(compare(self.get(result.puzzle.day, result.part)?, &current))
+    }
+
+    fn parse(s: &str) -> Result<Self, Error> {
+        let mut baseline = Self::default();
+
+        for line in s.lines().filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [day, part, mean, std_dev, samples] = fields[..] else {
+                return Err(Error::Parser(format!("malformed line \"{line}\"")));
+            };
+            let invalid = |field: &str| Error::Parser(format!("invalid {field} in \"{line}\""));
+
+            baseline.entries.insert(
+                (
+                    day.parse().map_err(|_| invalid("day"))?,
+                    part.parse().map_err(|_| invalid("part"))?,
+                ),
+                Entry {
+                    mean: Duration::from_nanos(mean.parse().map_err(|_| invalid("mean"))?),
+                    std_dev: Duration::from_nanos(
+                        std_dev.parse().map_err(|_| invalid("standard deviation"))?,
+                    ),
+                    samples: samples.parse().map_err(|_| invalid("sample count"))?,
+                },
+            );
+        }
+
+        Ok(baseline)
+    }
+
+    fn serialize(&self) -> String {
+        let mut lines = vec!["# day\tpart\tmean_ns\tstd_dev_ns\tsamples".to_string()];
+        for ((day, part), entry) in &self.entries {
+            lines.push(format!(
+                "{day}\t{part}\t{}\t{}\t{}",
+                entry.mean.as_nanos(),
+                entry.std_dev.as_nanos(),
+                entry.samples
+            ));
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Compares two timings with Welch's t-test.
+///
+/// A change is only flagged if it is significant at the 95% level and larger than the noise threshold.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn compare(baseline: &Entry, current: &Entry) -> Comparison {
+    let mean_base = baseline.mean.as_nanos() as f64;
+    let mean_current = current.mean.as_nanos() as f64;
+
+    let change = if mean_base > 0.0 {
+        (mean_current - mean_base) / mean_base
+    } else {
+        0.0
+    };
+
+    let std_err = ((baseline.std_dev.as_nanos() as f64).powi(2) / baseline.samples.max(1) as f64
+        + (current.std_dev.as_nanos() as f64).powi(2) / current.samples.max(1) as f64)
+        .sqrt();
+
+    let is_significant = if std_err > 0.0 {
+        ((mean_current - mean_base) / std_err).abs() > CRITICAL_VALUE
+    } else {
+        mean_current != mean_base
+    };
+
+    let verdict = if !is_significant || change.abs() < NOISE_THRESHOLD {
+        Verdict::NoChange
+    } else if change > 0.0 {
+        Verdict::Regressed
+    } else {
+        Verdict::Improved
+    };
+
+    Comparison { change, verdict }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let change = self.change * 100.0;
+        match self.verdict {
+            Verdict::Improved => write!(f, "{change:+.2}% (improved)"),
+            Verdict::Regressed => write!(f, "{ANSI_BOLD}{change:+.2}% (regressed){ANSI_RESET}"),
+            Verdict::NoChange => write!(f, "{change:+.2}% (no change)"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, is_valid_name, Baseline, Entry, Error, Verdict};
+    use crate::{day, year};
+
+    fn entry(mean: u64, std_dev: u64) -> Entry {
+        Entry {
+            mean: Duration::from_nanos(mean),
+            std_dev: Duration::from_nanos(std_dev),
+            samples: 100,
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let s =
+            "# day\tpart\tmean_ns\tstd_dev_ns\tsamples\n01\t1\t1000\t10\t100\n25\t2\t42\t0\t10\n";
+        let baseline = Baseline::parse(s).unwrap();
+        assert_eq!(baseline.get(day!(1), 1), Some(&entry(1000, 10)));
+        assert_eq!(baseline.get(day!(25), 2).unwrap().samples, 10);
+        assert_eq!(baseline.get(day!(2), 1), None);
+        assert_eq!(baseline.serialize(), s);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(Baseline::parse("01\t1\t1000").is_err());
+        assert!(Baseline::parse("26\t1\t1000\t10\t100").is_err());
+    }
+
+    #[test]
+    fn flags_significant_regressions() {
+        let result = compare(&entry(1000, 10), &entry(1500, 10));
+        assert_eq!(result.verdict, Verdict::Regressed);
+        assert!((result.change - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn flags_significant_improvements() {
+        let result = compare(&entry(1000, 10), &entry(500, 10));
+        assert_eq!(result.verdict, Verdict::Improved);
+    }
+
+    #[test]
+    fn ignores_noise() {
+        // within the standard error.
+        assert_eq!(
+            compare(&entry(1000, 500), &entry(1050, 500)).verdict,
+            Verdict::NoChange
+        );
+        // significant, but below the noise threshold.
+        assert_eq!(
+            compare(&entry(1000, 1), &entry(1010, 1)).verdict,
+            Verdict::NoChange
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        assert!(is_valid_name("main"));
+        assert!(is_valid_name("before_refactor-2"));
+        for name in ["", "../../x", "a/b", "a.tsv", "with space"] {
+            assert!(!is_valid_name(name), "{name}");
+        }
+        assert!(matches!(
+            Baseline::default().save(year!(2023), "../../x"),
+            Err(Error::InvalidName(_))
+        ));
+        assert!(matches!(
+            Baseline::load(year!(2023), "../x"),
+            Err(Error::InvalidName(_))
+        ));
+    }
+}
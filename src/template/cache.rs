@@ -0,0 +1,51 @@
+/// Module that keeps downloaded inputs and puzzle descriptions around, so they are only fetched once.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::template::get_data_path;
+use crate::PuzzleId;
+
+/// The downloaded files of one puzzle, keyed on year and day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PuzzleCache {
+    pub puzzle: PuzzleId,
+}
+
+impl PuzzleCache {
+    #[must_use]
+    pub fn new(puzzle: PuzzleId) -> Self {
+        Self { puzzle }
+    }
+
+    #[must_use]
+    pub fn input_path(&self) -> PathBuf {
+        get_data_path(self.puzzle, "inputs", "txt")
+    }
+
+    #[must_use]
+    pub fn puzzle_path(&self) -> PathBuf {
+        get_data_path(self.puzzle, "puzzles", "md")
+    }
+
+    /// Whether both the input and the puzzle have been downloaded.
+    /// Empty files, e.g. the input placeholder created by `scaffold`, do not count.
+    #[must_use]
+    pub fn is_cached(&self) -> bool {
+        is_present(&self.input_path()) && is_present(&self.puzzle_path())
+    }
+
+    /// Writes a downloaded input and puzzle description to the cache.
+    pub fn store(&self, input: &str, puzzle: &str) -> std::io::Result<()> {
+        for (path, contents) in [(self.input_path(), input), (self.puzzle_path(), puzzle)] {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, contents)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_present(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.is_file() && metadata.len() > 0)
+}
@@ -1,254 +1,355 @@
-use std::io;
-
 use crate::template::{
+    output,
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    runner::{self, PartResult, RunOptions, PARSE_PART},
+    Solution, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Day, DaySet, PuzzleId, Year};
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// The outcome of running a single day.
+enum DayOutcome {
+    NotSolved,
+    MissingInput,
+    Solved(Vec<PartResult>),
+}
 
-pub fn handle(is_release: bool, is_timed: bool) {
+/// Runs the registered solutions of the given days of a year in-process.
+///
+/// Days run on `jobs` threads and are printed in day order. Unless `jobs` is passed explicitly,
+/// timed runs are serial, so that concurrent days don't disturb the benchmarks.
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    days: DaySet,
+    is_release: bool,
+    jobs: Option<NonZeroUsize>,
+    options: &RunOptions,
+) {
     let mut timings: Vec<Timings> = vec![];
+    let mut records: Vec<PartResult> = vec![];
+    let is_text = options.format.is_text();
+    let is_timed = options.time;
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
+    if options.input.is_fixed() {
+        eprintln!("`--input` can only be used when solving a single day.");
+        process::exit(1);
+    }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    let baseline = runner::load_baseline(year, options);
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let jobs = match jobs {
+        Some(jobs) => jobs.get(),
+        None if is_timed => 1,
+        None => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+    };
+
+    let mut on_finished = |day: Day, outcome: DayOutcome| {
+        let puzzle = PuzzleId::new(year, day);
+
+        let results = match outcome {
+            DayOutcome::Solved(results) => results,
+            DayOutcome::NotSolved => {
+                if is_text {
+                    println!("Not solved.");
+                }
+                return;
+            }
+            DayOutcome::MissingInput => {
+                if is_text {
+                    println!("Missing input.");
+                }
+                return;
+            }
+        };
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+        if let Some(baseline) = &baseline {
+            runner::print_comparisons(baseline, &results, options);
         }
-    });
+
+        timings.push(collect_timings(&results, puzzle));
+        records.extend(results);
+    };
+
+    let first_day = days.iter().next();
+
+    if jobs == 1 {
+        days.iter().for_each(|day| {
+            if is_text {
+                print_header(day, Some(day) == first_day);
+            }
+            on_finished(day, run_day(solutions, PuzzleId::new(year, day), options));
+        });
+    } else {
+        let silent_options = RunOptions {
+            silent: true,
+            ..options.clone()
+        };
+
+        run_concurrently(
+            days,
+            jobs,
+            |day| run_day(solutions, PuzzleId::new(year, day), &silent_options),
+            |day, outcome| {
+                if is_text {
+                    print_header(day, Some(day) == first_day);
+                }
+                if let DayOutcome::Solved(results) = &outcome {
+                    for result in results {
+                        runner::print_part_result(result, options.format);
+                    }
+                }
+                on_finished(day, outcome);
+            },
+        );
+    }
+
+    output::print_records(&records, options.format);
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if is_text {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
 
-        if is_release {
+        // the table is regenerated from the timings, a subset of days would drop the others.
+        if is_release && days != DaySet::all() {
+            eprintln!("Not updating readme with benchmarks, as only days {days} were run.");
+        } else if let Some(part) = options.part.filter(|_| is_release) {
+            eprintln!("Not updating readme with benchmarks, as only part {part} was run.");
+        } else if is_release {
             match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+                Ok(()) if is_text => println!("Successfully updated README with benchmarks."),
+                Ok(()) => {}
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
             }
         }
     }
-}
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
-}
+    runner::save_baseline(year, &records, options);
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+    if records.iter().any(PartResult::is_failed) {
+        if is_text {
+            println!(
+                "\n{ANSI_BOLD}Some parts failed or did not match the known answers.{ANSI_RESET}"
+            );
+        }
+        process::exit(1);
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+fn print_header(day: Day, is_first: bool) {
+    if !is_first {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+fn run_day(solutions: &[Solution], puzzle: PuzzleId, options: &RunOptions) -> DayOutcome {
+    let Some(solution) = solutions.iter().find(|s| s.puzzle == puzzle) else {
+        return DayOutcome::NotSolved;
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    let Ok([one, two]) = options.input.read(puzzle) else {
+        return DayOutcome::MissingInput;
+    };
 
-        if is_release {
-            args.push("--release");
-        }
+    DayOutcome::Solved((solution.run)([&one, &two], options))
+}
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+/// Runs the days on a pool of `jobs` threads. Finished days are passed to `on_finished` in day
+/// order, as soon as all days before them have finished as well.
+fn run_concurrently<T: Send>(
+    days: DaySet,
+    jobs: usize,
+    run: impl Fn(Day) -> T + Sync,
+    mut on_finished: impl FnMut(Day, T),
+) {
+    let days: Vec<Day> = days.iter().collect();
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let (days, next, run, sender) = (&days, &next, &run, sender.clone());
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    if sender.send((day, run(day))).is_err() {
+                        break;
+                    }
+                }
+            });
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        drop(sender);
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let mut pending = BTreeMap::new();
+        let mut days = days.iter().copied().peekable();
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        for (day, outcome) in receiver {
+            pending.insert(day, outcome);
+            while let Some(outcome) = days.peek().and_then(|day| pending.remove(day)) {
+                on_finished(days.next().unwrap(), outcome);
+            }
         }
+    });
+}
 
-        thread.join().unwrap();
-        cmd.wait()?;
+/// Collects the timings of all benched parts of a day, and the memory usage of all measured parts.
+fn collect_timings(results: &[PartResult], puzzle: PuzzleId) -> Timings {
+    let mut timings = Timings {
+        puzzle,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        parse_stats: None,
+        part_1_stats: None,
+        part_2_stats: None,
+        part_1_memory: None,
+        part_2_memory: None,
+        total_nanos: 0_f64,
+    };
 
-        Ok(output)
+    for result in results {
+        match result.part {
+            1 => timings.part_1_memory = result.memory,
+            2 => timings.part_2_memory = result.memory,
+            _ => {}
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+    results
+        .iter()
+        .filter(|result| result.is_benched())
+        .for_each(|result| {
+            match result.part {
+                PARSE_PART => {
+                    timings.parse = Some(result.duration_str());
+                    timings.parse_stats = result.stats;
                 }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                1 => {
+                    timings.part_1 = Some(result.duration_str());
+                    timings.part_1_stats = result.stats;
+                }
+                2 => {
+                    timings.part_2 = Some(result.duration_str());
+                    timings.part_2_stats = result.stats;
                 }
+                _ => {}
+            }
 
-                timings.total_nanos += nanos;
-            });
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += result.duration.as_nanos() as f64;
+            }
+        });
 
-        timings
-    }
+    timings
+}
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{collect_timings, run_concurrently};
+    use crate::template::memory::MemoryStats;
+    use crate::template::runner::{PartResult, PARSE_PART};
+    use crate::{all_days, puzzle, Day, DaySet};
+
+    fn part(part: u8, nanos: u64, samples: u128) -> PartResult {
+        PartResult {
+            puzzle: puzzle!(2023, 1),
+            part,
+            answer: Some("0".into()),
+            duration: Duration::from_nanos(nanos),
+            samples,
+            stats: None,
+            verification: None,
+            error: None,
+            memory: None,
+        }
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    #[test]
+    fn test_well_formed() {
+        let res = collect_timings(
+            &[part(1, 74, 100000), part(2, 74_130_000, 99999)],
+            puzzle!(2023, 1),
+        );
+        assert_eq!(res.total_nanos, 74130074_f64);
+        assert_eq!(res.part_1.unwrap(), "74.0ns");
+        assert_eq!(res.part_2.unwrap(), "74.1ms");
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn test_unbenched_parts() {
+        let res = collect_timings(&[part(1, 74, 1), part(2, 100, 1)], puzzle!(2023, 1));
+        assert_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn test_parse_timings() {
+        let res = collect_timings(
+            &[
+                part(PARSE_PART, 1_000, 100),
+                part(1, 74, 100),
+                part(2, 100, 100),
+            ],
+            puzzle!(2023, 1),
+        );
+        assert_eq!(res.total_nanos, 1174_f64);
+        assert_eq!(res.parse.unwrap(), "1.0µs");
+        assert_eq!(res.part_1.unwrap(), "74.0ns");
+    }
 
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn test_memory_of_unbenched_parts() {
+        let memory = MemoryStats {
+            peak_bytes: 1024,
+            total_bytes: 2048,
+            allocations: 2,
+        };
+        let one = PartResult {
+            memory: Some(memory),
+            ..part(1, 74, 1)
+        };
+        let res = collect_timings(&[one, part(2, 100, 1)], puzzle!(2023, 1));
+        assert_eq!(res.part_1_memory, Some(memory));
+        assert_eq!(res.part_2_memory, None);
+        assert_eq!(res.part_1.is_none(), true);
+    }
 
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn test_concurrent_days_in_order() {
+        let mut finished: Vec<Day> = vec![];
+        run_concurrently(
+            DaySet::all(),
+            4,
+            |day| {
+                // finish later days first.
+                thread::sleep(Duration::from_millis(u64::from(25 - day.into_inner())));
+                day
+            },
+            |day, result| {
+                assert_eq!(day, result);
+                finished.push(day);
+            },
+        );
+        assert_eq!(finished, all_days().collect::<Vec<_>>());
     }
 }
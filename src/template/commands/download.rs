@@ -1,15 +1,46 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_client::AocClient;
+use crate::template::cache::PuzzleCache;
+use crate::{DaySet, PuzzleId, Year};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Downloads the given days one after another.
+pub fn handle(year: Year, days: DaySet, force: bool) {
+    let client = AocClient::from_env();
+
+    days.iter()
+        .for_each(|day| download(&client, PuzzleId::new(year, day), force));
+}
+
+fn download(client: &AocClient, puzzle: PuzzleId, force: bool) {
+    let cache = PuzzleCache::new(puzzle);
+    let input_path = cache.input_path();
+    let puzzle_path = cache.puzzle_path();
+
+    if cache.is_cached() && !force {
+        println!(
+            "🎄 Day {} of {} is already downloaded, pass `--force` to download it again.",
+            puzzle.day, puzzle.year
+        );
+        return;
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let downloaded = client.puzzle(puzzle).and_then(|description| {
+        let input = client.input(puzzle)?;
+        cache.store(&input, &description)?;
+        Ok(())
+    });
+
+    if let Err(e) = downloaded {
+        eprintln!("failed to download {puzzle}: {e}");
         process::exit(1);
     };
+
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
 }
@@ -1,16 +1,28 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_client::{html_to_text, AocClient};
+use crate::template::cache::PuzzleCache;
+use crate::PuzzleId;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(puzzle: PuzzleId) {
+    let client = AocClient::from_env();
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let description = match client.puzzle(puzzle) {
+        Ok(description) => description,
+        Err(e) => {
+            eprintln!("failed to read {puzzle}: {e}");
+            process::exit(1);
+        }
     };
+
+    // always fetched, part two only shows up once part one is solved.
+    let puzzle_path = PuzzleCache::new(puzzle).puzzle_path();
+    if let Err(e) = fs::write(&puzzle_path, &description) {
+        eprintln!(
+            "failed to write puzzle to \"{}\": {e}",
+            puzzle_path.display()
+        );
+    }
+
+    println!("{}", html_to_text(&description));
 }
@@ -1,19 +1,24 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::cache::PuzzleCache;
+use crate::template::examples::{extract_examples, PartExample};
+use crate::template::{get_bin_path, get_data_part_path, get_data_path};
+use crate::{DaySet, PuzzleId, Year};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+const MODULE_TEMPLATE: &str = r#"use advent_of_code::template::{Answer, SolveError};
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+pub fn part_one(input: &str) -> Result<Answer, SolveError> {
+    Err(SolveError::Unsolved)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Result<Answer, SolveError> {
+    Err(SolveError::Unsolved)
 }
 
 #[cfg(test)]
@@ -22,30 +27,129 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_one(PART_ONE_EXAMPLE);
+        assert_eq!(result, PART_ONE_ANSWER);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(PART_TWO_EXAMPLE);
+        assert_eq!(result, PART_TWO_ANSWER);
     }
 }
 "#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+const READ_EXAMPLE: &str = r#"&advent_of_code::template::read_file("examples", PUZZLE)"#;
+
+const READ_EXAMPLE_PART: &str = r#"&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, PART,
+        )"#;
+
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Creates an empty file, unless it already exists. Returns whether the file was created.
+fn create_empty_file(path: &Path) -> Result<bool, std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    match safe_create_file(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes an example file, unless it already has contents. Returns whether the file was written.
+fn write_example(path: &Path, example: Option<&str>) -> Result<bool, std::io::Error> {
+    let mut file = create_file(path)?;
+    if file.metadata()?.len() > 0 {
+        return Ok(false);
+    }
+    file.write_all(example.unwrap_or_default().as_bytes())?;
+    Ok(true)
+}
+
+/// The expected answer to fill into a scaffolded test, as an expression.
+fn expected_answer(example: &PartExample) -> String {
+    let Some(answer) = example.answer.as_deref() else {
+        return "Err(SolveError::Unsolved)".into();
+    };
+
+    // integer literals need a suffix once they no longer fit the default `i32`.
+    let literal = if answer.parse::<i32>().is_ok() {
+        answer.to_string()
+    } else if answer.parse::<i64>().is_ok() {
+        format!("{answer}_i64")
+    } else if answer.parse::<i128>().is_ok() {
+        format!("{answer}_i128")
+    } else if answer.parse::<u128>().is_ok() {
+        format!("{answer}_u128")
+    } else {
+        format!("{answer:?}")
+    };
+
+    format!("Ok(Answer::from({literal}))")
+}
+
+/// Scaffolds the given days. When scaffolding several days, days that already have a module are
+/// skipped instead of failing.
+pub fn handle(year: Year, days: DaySet) {
+    for day in days.iter() {
+        let puzzle = PuzzleId::new(year, day);
+
+        if days.len() > 1 && get_bin_path(puzzle).exists() {
+            println!(
+                "Skipping day {day}, module file \"{}\" already exists.",
+                get_bin_path(puzzle).display()
+            );
+            continue;
+        }
+
+        scaffold(puzzle);
+    }
+}
+
+fn scaffold(puzzle: PuzzleId) {
+    let input_path = get_data_path(puzzle, "inputs", "txt");
+    let module_path = get_bin_path(puzzle);
+
+    // the puzzle description is only present if the day has been downloaded before.
+    let examples = fs::read_to_string(PuzzleCache::new(puzzle).puzzle_path())
+        .map(|html| extract_examples(&html))
+        .unwrap_or_default();
+    let part_one = examples.first().cloned().unwrap_or_default();
+    let part_two = examples.get(1).cloned().unwrap_or_default();
+    let has_part_examples = part_two.input.is_some() && part_two.input != part_one.input;
+
+    let example_files = if has_part_examples {
+        vec![
+            (get_data_part_path(puzzle, "examples", 1), &part_one),
+            (get_data_part_path(puzzle, "examples", 2), &part_two),
+        ]
+    } else {
+        vec![(get_data_path(puzzle, "examples", "txt"), &part_one)]
+    };
+
+    let read_example = |part: u8| {
+        if has_part_examples {
+            READ_EXAMPLE_PART.replace("PART", &part.to_string())
+        } else {
+            READ_EXAMPLE.into()
+        }
+    };
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -57,11 +161,16 @@ pub fn handle(day: Day) {
 
     match file.write_all(
         MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
+            .replace("YEAR_NUMBER", &puzzle.year.into_inner().to_string())
+            .replace("DAY_NUMBER", &puzzle.day.into_inner().to_string())
+            .replace("PART_ONE_EXAMPLE", &read_example(1))
+            .replace("PART_TWO_EXAMPLE", &read_example(2))
+            .replace("PART_ONE_ANSWER", &expected_answer(&part_one))
+            .replace("PART_TWO_ANSWER", &expected_answer(&part_two))
             .as_bytes(),
     ) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -69,26 +178,46 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    match create_empty_file(&input_path) {
+        Ok(true) => {
+            println!("Created empty input file \"{}\"", input_path.display());
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    for (example_path, example) in example_files {
+        match write_example(&example_path, example.input.as_deref()) {
+            Ok(true) if example.input.is_some() => {
+                println!("Created example file \"{}\"", example_path.display());
+            }
+            Ok(true) => {
+                println!("Created empty example file \"{}\"", example_path.display());
+            }
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    }
+
+    for (part, example) in [(1, &part_one), (2, &part_two)] {
+        if let Some(answer) = &example.answer {
+            println!("Expected example answer of part {part}: {answer}");
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    if Year::from_env() == Some(puzzle.year) {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
+    }
 }
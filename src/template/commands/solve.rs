@@ -1,31 +1,70 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::Day;
+use crate::template::get_bin_path;
+use crate::template::output::OutputFormat;
+use crate::template::runner::RunOptions;
+use crate::{DaySet, PuzzleId, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Runs the binaries of the given days one after another. When solving several days, days without
+/// a solution are skipped.
+pub fn handle(year: Year, days: DaySet, release: bool, options: &RunOptions) {
+    if options.submit.is_some() && days.len() > 1 {
+        eprintln!("`--submit` can only be used when solving a single day.");
+        process::exit(1);
+    }
 
-    if release {
-        cmd_args.push("--release".to_string());
+    if options.input.is_fixed() && days.len() > 1 {
+        eprintln!("`--input` can only be used when solving a single day.");
+        process::exit(1);
     }
 
-    cmd_args.push("--".to_string());
+    // each day prints its own array, which together would not be valid JSON.
+    if options.format == OutputFormat::Json && days.len() > 1 {
+        eprintln!("`--format json` can only be used when solving a single day, use `--format ndjson` instead.");
+        process::exit(1);
+    }
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    let mut is_failed = false;
+
+    for day in days.iter() {
+        let puzzle = PuzzleId::new(year, day);
+
+        if days.len() > 1 && !get_bin_path(puzzle).exists() {
+            continue;
+        }
+
+        is_failed |= !solve(puzzle, release, options);
     }
 
-    if time {
-        cmd_args.push("--time".to_string());
+    if is_failed {
+        process::exit(1);
     }
+}
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+/// Runs the binary of a day, returns whether it succeeded.
+fn solve(puzzle: PuzzleId, release: bool, options: &RunOptions) -> bool {
+    let mut cmd = command(puzzle, release, options)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    cmd.wait().unwrap().success()
+}
+
+/// The `cargo` invocation that builds and runs the binary of a day.
+#[must_use]
+pub fn command(puzzle: PuzzleId, release: bool, options: &RunOptions) -> Command {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
+
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args.push("--".to_string());
+    cmd_args.extend(options.to_args());
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args);
+    cmd
 }
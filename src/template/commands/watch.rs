@@ -0,0 +1,264 @@
+/// Module that re-runs a day whenever its solution, the shared code or its data changes.
+///
+/// Changes are detected by polling modification times, so no platform-specific file system
+/// notifications are needed.
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+use crate::template::commands::solve;
+use crate::template::input::InputSource;
+use crate::template::output::{self, OutputFormat, Record};
+use crate::template::runner::{part_label, RunOptions, PARSE_PART};
+use crate::template::{
+    get_bin_path, get_data_path, get_year_path, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{DaySet, PuzzleId, Year};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification times of all watched files.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Builds, tests and runs a day, and does so again whenever one of its files changes.
+pub fn handle(year: Year, days: DaySet, release: bool, options: &RunOptions) {
+    let Some(day) = days.iter().next().filter(|_| days.len() == 1) else {
+        eprintln!("`--watch` can only be used when solving a single day.");
+        process::exit(1);
+    };
+
+    if options.submit.is_some() {
+        eprintln!("`--watch` can't be combined with `--submit`.");
+        process::exit(1);
+    }
+
+    if options.input == InputSource::Stdin {
+        eprintln!("`--watch` can't read the input from stdin.");
+        process::exit(1);
+    }
+
+    let puzzle = PuzzleId::new(year, day);
+    let options = RunOptions {
+        format: OutputFormat::Ndjson,
+        ..options.clone()
+    };
+
+    let mut answers: HashMap<u8, Option<String>> = HashMap::new();
+    let mut snapshot = watched_files(puzzle, &options.input);
+
+    loop {
+        run(puzzle, release, &options, &mut answers);
+        println!("\n👀 Watching for changes, press Ctrl-C to stop.");
+
+        let changed = loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = watched_files(puzzle, &options.input);
+            let changed = changed_files(&snapshot, &next);
+            snapshot = next;
+
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+
+        let changed: Vec<String> = changed.iter().map(|p| p.display().to_string()).collect();
+        println!("\n{ANSI_BOLD}Changed:{ANSI_RESET} {}", changed.join(", "));
+    }
+}
+
+/// Builds the day, runs its tests and then the solution, printing how answers changed.
+fn run(
+    puzzle: PuzzleId,
+    release: bool,
+    options: &RunOptions,
+    answers: &mut HashMap<u8, Option<String>>,
+) {
+    if !cargo(puzzle, "build", release) {
+        println!("Build failed.");
+        return;
+    }
+
+    println!("{ANSI_BOLD}Tests{ANSI_RESET}");
+    cargo(puzzle, "test", release);
+
+    println!("{ANSI_BOLD}Solution{ANSI_RESET}");
+    let output = solve::command(puzzle, release, options)
+        .env("CARGO_TERM_QUIET", "true")
+        .stderr(Stdio::inherit())
+        .output();
+
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run solution: {e}");
+            return;
+        }
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(output::parse_record)
+        .for_each(|record| {
+            print_record(&record, answers.get(&record.part));
+            answers.insert(record.part, record.answer);
+        });
+}
+
+/// Runs a cargo command like `build` for the binary of a day, returns whether it succeeded.
+fn cargo(puzzle: PuzzleId, command: &str, release: bool) -> bool {
+    let mut cmd = Command::new("cargo");
+    cmd.args([command, "--bin", &puzzle.to_string()])
+        .env("CARGO_TERM_QUIET", "true");
+
+    if release {
+        cmd.arg("--release");
+    }
+
+    cmd.status().is_ok_and(|status| status.success())
+}
+
+/// Prints the result of a part, along with the answer of the previous run if it changed.
+fn print_record(record: &Record, previous: Option<&Option<String>>) {
+    if record.part == PARSE_PART {
+        let mark = if record.error.is_some() { "✖" } else { "✔" };
+        println!(
+            "{}: {mark} ({:.1?})",
+            part_label(record.part),
+            record.duration
+        );
+        if let Some(error) = &record.error {
+            println!("{error}");
+        }
+        return;
+    }
+
+    let answer = record.answer.as_deref().unwrap_or("✖");
+    let is_multiline = answer.contains('\n');
+
+    let mut suffix = format!(" ({:.1?})", record.duration);
+    match record.status.as_str() {
+        "correct" => suffix.push_str(" ✔"),
+        "incorrect" => suffix.push_str(" ✘"),
+        _ => {}
+    }
+    match previous {
+        Some(previous) if *previous == record.answer => {
+            suffix.push_str(&format!(" {ANSI_ITALIC}unchanged{ANSI_RESET}"));
+        }
+        Some(previous) => {
+            let previous = previous.as_deref().unwrap_or("✖");
+            if is_multiline || previous.contains('\n') {
+                suffix.push_str(&format!(" {ANSI_ITALIC}changed{ANSI_RESET}"));
+            } else {
+                suffix.push_str(&format!(" {ANSI_ITALIC}was {previous}{ANSI_RESET}"));
+            }
+        }
+        None => {}
+    }
+
+    if is_multiline {
+        println!("Part {}: ▼{suffix}", record.part);
+        println!("{answer}");
+    } else {
+        println!(
+            "Part {}: {ANSI_BOLD}{answer}{ANSI_RESET}{suffix}",
+            record.part
+        );
+    }
+
+    if let Some(error) = &record.error {
+        println!("{error}");
+    }
+}
+
+/// Collects the files that affect a day: the shared code, its binary, examples and input.
+fn watched_files(puzzle: PuzzleId, input: &InputSource) -> Snapshot {
+    let mut files = Snapshot::new();
+    let bin_path = get_bin_path(puzzle);
+    let day = puzzle.day.to_string();
+
+    add_dir(&mut files, Path::new("src"), &|path| {
+        path.extension().is_some_and(|ext| ext == "rs")
+            && (path == bin_path || !bin_path.parent().is_some_and(|bin| path.starts_with(bin)))
+    });
+    add_file(&mut files, PathBuf::from("Cargo.toml"));
+
+    add_dir(
+        &mut files,
+        &get_year_path(puzzle.year).join("examples"),
+        &|path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with(&day))
+        },
+    );
+    add_file(&mut files, get_data_path(puzzle, "inputs", "txt"));
+
+    if let InputSource::File(path) = input {
+        add_file(&mut files, path.clone());
+    }
+
+    files
+}
+
+fn add_dir(files: &mut Snapshot, dir: &Path, filter: &dyn Fn(&Path) -> bool) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            add_dir(files, &path, filter);
+        } else if filter(&path) {
+            add_file(files, path);
+        }
+    }
+}
+
+/// Adds a file, unless it does not exist. Creating it later is then detected as a change.
+fn add_file(files: &mut Snapshot, path: PathBuf) {
+    if let Ok(modified) = fs::metadata(&path).and_then(|metadata| metadata.modified()) {
+        files.insert(path, modified);
+    }
+}
+
+/// The files that were created, modified or removed between two snapshots.
+fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let changed: BTreeSet<&PathBuf> = before
+        .keys()
+        .chain(after.keys())
+        .filter(|path| before.get(*path) != after.get(*path))
+        .collect();
+
+    changed.into_iter().cloned().collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    use super::{changed_files, Snapshot};
+
+    #[test]
+    fn detects_changed_files() {
+        let time = SystemTime::UNIX_EPOCH;
+        let before = Snapshot::from([
+            (PathBuf::from("a.rs"), time),
+            (PathBuf::from("b.rs"), time),
+            (PathBuf::from("c.rs"), time),
+        ]);
+        let after = Snapshot::from([
+            (PathBuf::from("a.rs"), time),
+            (PathBuf::from("b.rs"), time + Duration::from_secs(1)),
+            (PathBuf::from("d.rs"), time),
+        ]);
+
+        assert_eq!(
+            changed_files(&before, &after),
+            ["b.rs", "c.rs", "d.rs"].map(PathBuf::from)
+        );
+        assert!(changed_files(&after, &after).is_empty());
+    }
+}
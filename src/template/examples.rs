@@ -0,0 +1,105 @@
+/// Module that extracts example inputs and their expected answers from a downloaded puzzle.
+///
+/// Every part of a puzzle is an `<article>`, examples are the `<pre><code>` blocks in it and the
+/// expected answer is usually the last emphasized code span, e.g. `<code><em>142</em></code>`.
+use crate::template::aoc_client::{decode_entities, extract_articles};
+
+/// The example of one part of a puzzle.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PartExample {
+    /// The first code block of the part, if it has one.
+    pub input: Option<String>,
+    /// The answer the part expects for its example.
+    pub answer: Option<String>,
+}
+
+/// Extracts the example of every part that is present in the puzzle description.
+#[must_use]
+pub fn extract_examples(html: &str) -> Vec<PartExample> {
+    extract_articles(html)
+        .into_iter()
+        .map(|article| PartExample {
+            input: find_between(article, "<pre><code>", "</code></pre>")
+                .first()
+                .map(|block| decode_entities(&strip_tags(block))),
+            answer: find_between(article, "<code><em>", "</em></code>")
+                .last()
+                .map(|answer| decode_entities(&strip_tags(answer)))
+                .filter(|answer| !answer.trim().is_empty()),
+        })
+        .collect()
+}
+
+/// Returns the contents of every `start ... end` section of a string.
+fn find_between<'a>(s: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = s;
+
+    while let Some(pos) = rest.find(start) {
+        rest = &rest[pos + start.len()..];
+        let Some(len) = rest.find(end) else {
+            break;
+        };
+        found.push(&rest[..len]);
+        rest = &rest[len + end.len()..];
+    }
+
+    found
+}
+
+/// Removes tags like `<em>` that are used for highlighting inside code blocks.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut is_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => is_tag = true,
+            '>' if is_tag => is_tag = false,
+            c if !is_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_examples, PartExample};
+
+    const PUZZLE: &str = "<article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>\
+        <p>For example:</p><pre><code>1abc2\npqr3stu8vwx\n</code></pre>\
+        <p>The calibration values are <code>12</code> and <code>38</code>.</p>\
+        <p>Adding these together produces <code><em>50</em></code>.</p></article>\n\n\
+        <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\
+        <pre><code>two1nine\n<em>a</em>&lt;b&gt;\n</code></pre>\
+        <p>Adding these together produces <code><em>29</em></code>.</p></article>";
+
+    #[test]
+    fn extracts_examples_of_both_parts() {
+        assert_eq!(
+            extract_examples(PUZZLE),
+            vec![
+                PartExample {
+                    input: Some("1abc2\npqr3stu8vwx\n".into()),
+                    answer: Some("50".into()),
+                },
+                PartExample {
+                    input: Some("two1nine\na<b>\n".into()),
+                    answer: Some("29".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_parts_without_examples() {
+        let examples = extract_examples(
+            "<article><p>No example, but the answer is <code><em>7</em></code>.</p></article>",
+        );
+        assert_eq!(examples[0].input, None);
+        assert_eq!(examples[0].answer.as_deref(), Some("7"));
+        assert!(extract_examples("<p>not downloaded</p>").is_empty());
+    }
+}
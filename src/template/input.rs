@@ -0,0 +1,152 @@
+/// Module that reads the input a solution is run against.
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{fs, process};
+
+use crate::template::{get_data_part_path, get_data_path};
+use crate::PuzzleId;
+
+/// Where a solution reads its input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/<year>/inputs`.
+    #[default]
+    Puzzle,
+    /// The examples in `data/<year>/examples`. Each part reads its own example, if it has one.
+    Examples,
+    /// The example of one part, e.g. `data/<year>/examples/01-2.txt`, for both parts.
+    Example(u8),
+    /// A file at the given path.
+    File(PathBuf),
+    /// The standard input.
+    Stdin,
+}
+
+impl InputSource {
+    /// Parses `--input <path>` and `--example [N]`, where `--input -` reads the standard input.
+    ///
+    /// `--example` takes an optional value, which pico-args has no notion of. It is parsed after
+    /// every other option, so a value following it can only be the part number.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let input: Option<PathBuf> = args.opt_value_from_str("--input")?;
+
+        let example = match args.opt_value_from_str("--example") {
+            Ok(part) => part.map(Self::Example),
+            Err(pico_args::Error::OptionWithoutAValue(_)) => {
+                args.contains("--example");
+                Some(Self::Examples)
+            }
+            Err(e) => return Err(e),
+        };
+
+        match (input, example) {
+            (Some(_), Some(_)) => Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "`--input` and `--example` can't be combined".into(),
+            }),
+            (Some(path), None) if path.as_os_str() == "-" => Ok(Self::Stdin),
+            (Some(path), None) => Ok(Self::File(path)),
+            (None, Some(example)) => Ok(example),
+            (None, None) => Ok(Self::Puzzle),
+        }
+    }
+
+    /// Whether the input is the same for every day, i.e. a file or the standard input.
+    #[must_use]
+    pub fn is_fixed(&self) -> bool {
+        matches!(self, Self::File(_) | Self::Stdin)
+    }
+
+    /// Converts the source back into the arguments of a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Examples => vec!["--example".into()],
+            Self::Example(part) => vec!["--example".into(), part.to_string()],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Reads the inputs of part one and two.
+    pub fn read(&self, puzzle: PuzzleId) -> io::Result<[String; 2]> {
+        let input = match self {
+            Self::Puzzle => fs::read_to_string(get_data_path(puzzle, "inputs", "txt"))?,
+            Self::Examples => {
+                return Ok([read_example(puzzle, 1)?, read_example(puzzle, 2)?]);
+            }
+            Self::Example(part) => {
+                fs::read_to_string(get_data_part_path(puzzle, "examples", *part))?
+            }
+            Self::File(path) => fs::read_to_string(path)?,
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                input
+            }
+        };
+
+        Ok([input.clone(), input])
+    }
+
+    /// Reads the inputs of part one and two, exits if they can't be read.
+    #[must_use]
+    pub fn read_or_exit(&self, puzzle: PuzzleId) -> [String; 2] {
+        match self.read(puzzle) {
+            Ok(inputs) => inputs,
+            Err(e) => {
+                eprintln!("Failed to read input: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Reads the example of a part, falling back to the example shared by both parts.
+fn read_example(puzzle: PuzzleId, part: u8) -> io::Result<String> {
+    fs::read_to_string(get_data_part_path(puzzle, "examples", part))
+        .or_else(|_| fs::read_to_string(get_data_path(puzzle, "examples", "txt")))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    use super::InputSource;
+
+    fn parse(args: &[&str]) -> Result<InputSource, pico_args::Error> {
+        let mut args = pico_args::Arguments::from_vec(args.iter().map(OsString::from).collect());
+        let source = InputSource::parse(&mut args);
+        assert!(source.is_err() || args.finish().is_empty());
+        source
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse(&[]).unwrap(), InputSource::Puzzle);
+        assert_eq!(parse(&["--example"]).unwrap(), InputSource::Examples);
+        assert_eq!(parse(&["--example", "2"]).unwrap(), InputSource::Example(2));
+        assert_eq!(parse(&["--input", "-"]).unwrap(), InputSource::Stdin);
+        assert_eq!(
+            parse(&["--input", "edge.txt"]).unwrap(),
+            InputSource::File(PathBuf::from("edge.txt"))
+        );
+        assert!(parse(&["--example", "x"]).is_err());
+        assert!(parse(&["--example", "--input", "edge.txt"]).is_err());
+    }
+
+    #[test]
+    fn converts_input_sources_to_args() {
+        for source in [
+            InputSource::Examples,
+            InputSource::Example(1),
+            InputSource::Stdin,
+            InputSource::File(PathBuf::from("edge.txt")),
+        ] {
+            let args = source.to_args();
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            assert_eq!(parse(&args).unwrap(), source);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+/// Module that counts heap allocations, to report the memory usage of solution parts.
+///
+/// The counting allocator is installed for every binary of the crate, but only counts while a
+/// measurement is running. Counters are kept per thread, so only the allocations of the measured
+/// thread are counted, not those of parts running concurrently or abandoned after a timeout.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Display;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// the allocator runs these, so they must be initialized without allocating.
+thread_local! {
+    static IS_COUNTING: Cell<bool> = const { Cell::new(false) };
+    /// Bytes allocated since the measurement started, minus the bytes freed since then.
+    /// Can be negative, if memory allocated before the measurement is freed.
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+    static TOTAL: Cell<usize> = const { Cell::new(0) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Heap usage of a part, measured by [`measure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// The largest amount of memory that was allocated at once.
+    pub peak_bytes: usize,
+    /// The sum of all allocations.
+    pub total_bytes: usize,
+    /// The number of allocations, including reallocations.
+    pub allocations: usize,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} in {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.allocations
+        )
+    }
+}
+
+/// Runs a function while counting the allocations of the current thread.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, MemoryStats) {
+    CURRENT.set(0);
+    PEAK.set(0);
+    TOTAL.set(0);
+    ALLOCATIONS.set(0);
+    IS_COUNTING.set(true);
+
+    let result = func();
+
+    IS_COUNTING.set(false);
+
+    let stats = MemoryStats {
+        peak_bytes: PEAK.get().unsigned_abs(),
+        total_bytes: TOTAL.get(),
+        allocations: ALLOCATIONS.get(),
+    };
+
+    (result, stats)
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Whether the current thread is being measured. Thread-locals are gone while a thread exits, its
+/// last allocations are never counted.
+fn is_counting() -> bool {
+    IS_COUNTING.try_with(Cell::get).unwrap_or(false)
+}
+
+fn count_alloc(size: usize) {
+    if is_counting() {
+        let size = isize::try_from(size).unwrap_or(isize::MAX);
+        let current = CURRENT.get().saturating_add(size);
+        CURRENT.set(current);
+        PEAK.set(PEAK.get().max(current));
+        TOTAL.set(TOTAL.get().saturating_add(size.unsigned_abs()));
+        ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+    }
+}
+
+fn count_dealloc(size: usize) {
+    if is_counting() {
+        let size = isize::try_from(size).unwrap_or(isize::MAX);
+        CURRENT.set(CURRENT.get().saturating_sub(size));
+    }
+}
+
+/// The system allocator, counting allocations while a measurement is running.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            count_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        count_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            count_dealloc(layout.size());
+            count_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::hint::black_box;
+    use std::thread;
+
+    use super::{format_bytes, measure};
+
+    #[test]
+    fn measures_allocations() {
+        let (_, stats) = measure(|| {
+            let mut v: Vec<u8> = Vec::with_capacity(4096);
+            v.push(1);
+            black_box(&v);
+            drop(v);
+            black_box(vec![0_u8; 1024]);
+        });
+
+        assert_eq!(stats.peak_bytes, 4096);
+        assert_eq!(stats.total_bytes, 5120);
+        assert_eq!(stats.allocations, 2);
+    }
+
+    #[test]
+    fn ignores_other_threads() {
+        let (_, stats) = measure(|| {
+            thread::scope(|scope| {
+                scope.spawn(|| black_box(vec![0_u8; 1 << 20]));
+            });
+        });
+
+        assert!(stats.total_bytes < 1 << 20);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(12), "12 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -1,48 +1,141 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{PuzzleId, Year};
+use runner::{PartResult, RunOptions};
 
-pub mod aoc_cli;
+pub use answer::Answer;
+pub use solve_error::{parse_input, SolveError, Span};
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+pub mod answer;
+pub mod answers;
+pub mod aoc_client;
+pub mod baseline;
+pub mod cache;
 pub mod commands;
+pub mod examples;
+pub mod input;
+pub mod memory;
+pub mod output;
 pub mod readme_benchmarks;
 pub mod runner;
+pub mod solve_error;
+pub mod stats;
+pub mod submissions;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// The data folder of an event, e.g. `data/2023`.
+#[must_use]
+pub fn get_year_path(year: Year) -> PathBuf {
+    PathBuf::from("data").join(year.to_string())
+}
+
+/// The path of a data file of a puzzle, e.g. `data/2023/inputs/01.txt`.
+#[must_use]
+pub fn get_data_path(puzzle: PuzzleId, folder: &str, extension: &str) -> PathBuf {
+    get_year_path(puzzle.year)
+        .join(folder)
+        .join(format!("{}.{extension}", puzzle.day))
+}
+
+/// The path of the binary of a puzzle, e.g. `src/bin/2023-01.rs`.
+#[must_use]
+pub fn get_bin_path(puzzle: PuzzleId) -> PathBuf {
+    PathBuf::from("src")
+        .join("bin")
+        .join(format!("{puzzle}.rs"))
+}
+
+/// The path of a data file of one part of a puzzle, e.g. `data/2023/examples/01-2.txt`.
+#[must_use]
+pub fn get_data_part_path(puzzle: PuzzleId, folder: &str, part: u8) -> PathBuf {
+    get_year_path(puzzle.year)
+        .join(folder)
+        .join(format!("{}-{part}.txt", puzzle.day))
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    try_read_file(folder, puzzle).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returning an error if it can't be read.
+pub fn try_read_file(folder: &str, puzzle: PuzzleId) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd.join(get_data_path(puzzle, folder, "txt"));
+    fs::read_to_string(filepath)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(get_data_part_path(puzzle, folder, part));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// A solution registered with the library by the [`solution!`] macro.
+///
+/// The main binary mounts every `src/bin/YYYY-DD.rs` as a module and collects these,
+/// which lets the `all` command run every day in a single process.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs both parts against the provided inputs of part one and two.
+    pub run: fn([&str; 2], &RunOptions) -> Vec<PartResult>,
+}
+
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// With a `parse` hook, e.g. `solution!(2023, 5, parse = parse_almanac)`, the input is parsed once
+/// by a function returning `Result<T, SolveError>`, and both parts take `&T` instead of `&str`.
+/// Parsing is then timed separately from the parts.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($year:expr, $day:expr) => {
+        advent_of_code::solution!(@solution $year, $day, |[one, two], options| {
+            use advent_of_code::template::runner::*;
+            let mut results = vec![];
+            if options.runs_part(1) {
+                results.push(run_part(part_one, one.into(), PUZZLE, 1, options));
+            }
+            if options.runs_part(2) {
+                results.push(run_part(part_two, two.into(), PUZZLE, 2, options));
+            }
+            results
+        });
+    };
+    ($year:expr, $day:expr, parse = $parse:expr) => {
+        advent_of_code::solution!(@solution $year, $day, |inputs, options| {
+            advent_of_code::template::runner::run_parsed(
+                $parse, part_one, part_two, inputs, PUZZLE, options,
+            )
+        });
+    };
+    (@solution $year:expr, $day:expr, $run:expr) => {
+        /// The current year.
+        #[allow(dead_code)]
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
         /// The current day.
+        #[allow(dead_code)]
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
+        /// The current puzzle.
+        const PUZZLE: advent_of_code::PuzzleId = advent_of_code::PuzzleId::new(YEAR, DAY);
+
+        /// Registers both parts of this puzzle with the library.
+        pub const SOLUTION: advent_of_code::template::Solution =
+            advent_of_code::template::Solution {
+                puzzle: PUZZLE,
+                run: $run,
+            };
 
+        #[allow(dead_code)]
         fn main() {
-            use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            advent_of_code::template::runner::run_main(&SOLUTION);
         }
     };
 }
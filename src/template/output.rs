@@ -0,0 +1,363 @@
+/// Machine-readable output of solution results.
+use std::error::Error;
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::template::memory::MemoryStats;
+use crate::template::runner::PartResult;
+use crate::template::stats::Stats;
+
+/// The format results are printed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable, ANSI-decorated lines.
+    #[default]
+    Text,
+    /// A single JSON array holding every record, printed once all parts have run.
+    Json,
+    /// One JSON record per line, printed as soon as a part has run.
+    Ndjson,
+}
+
+impl OutputFormat {
+    #[must_use]
+    pub fn is_text(self) -> bool {
+        self == Self::Text
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Text => "text",
+            Self::Json => "json",
+            Self::Ndjson => "ndjson",
+        })
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `text`, `json` or `ndjson`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Serializes a result into a single-line JSON object.
+#[must_use]
+pub fn to_json(result: &PartResult) -> String {
+    let answer = result
+        .answer
+        .as_deref()
+        .map_or_else(|| "null".into(), json_string);
+
+    let stats = result
+        .stats
+        .as_ref()
+        .map_or_else(|| "null".into(), stats_to_json);
+
+    let memory = result
+        .memory
+        .as_ref()
+        .map_or_else(|| "null".into(), memory_to_json);
+
+    let error = result
+        .error
+        .as_ref()
+        .map_or_else(|| "null".into(), |e| json_string(&e.to_string()));
+
+    format!(
+        r#"{{"year":{},"day":{},"part":{},"answer":{},"duration_ns":{},"samples":{},"status":"{}","stats":{},"memory":{},"error":{}}}"#,
+        result.puzzle.year.into_inner(),
+        result.puzzle.day.into_inner(),
+        result.part,
+        answer,
+        result.duration.as_nanos(),
+        result.samples,
+        result.status(),
+        stats,
+        memory,
+        error,
+    )
+}
+
+fn stats_to_json(stats: &Stats) -> String {
+    format!(
+        r#"{{"mean_ns":{},"median_ns":{},"std_dev_ns":{},"p5_ns":{},"p95_ns":{},"min_ns":{},"max_ns":{},"outliers":{}}}"#,
+        stats.mean.as_nanos(),
+        stats.median.as_nanos(),
+        stats.std_dev.as_nanos(),
+        stats.p5.as_nanos(),
+        stats.p95.as_nanos(),
+        stats.min.as_nanos(),
+        stats.max.as_nanos(),
+        stats.outliers.total(),
+    )
+}
+
+fn memory_to_json(memory: &MemoryStats) -> String {
+    format!(
+        r#"{{"peak_bytes":{},"total_bytes":{},"allocations":{}}}"#,
+        memory.peak_bytes, memory.total_bytes, memory.allocations,
+    )
+}
+
+/// Serializes a list of results into a JSON array.
+#[must_use]
+pub fn to_json_array(results: &[PartResult]) -> String {
+    let records: Vec<String> = results.iter().map(to_json).collect();
+    format!("[{}]", records.join(","))
+}
+
+/// Prints a record if records are streamed in the given format.
+pub fn print_record(result: &PartResult, format: OutputFormat) {
+    if format == OutputFormat::Ndjson {
+        println!("{}", to_json(result));
+    }
+}
+
+/// Prints all records if they are collected in the given format.
+pub fn print_records(results: &[PartResult], format: OutputFormat) {
+    if format == OutputFormat::Json {
+        println!("{}", to_json_array(results));
+    }
+}
+
+/// A record read back from its JSON serialization, e.g. from the output of a solution binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+/// Parses a record serialized by [`to_json`].
+///
+/// Fields are read in the order they are written in, so keys contained in strings like the
+/// answer are never mistaken for fields.
+#[must_use]
+pub fn parse_record(s: &str) -> Option<Record> {
+    let mut rest = s;
+
+    let part = take_field(&mut rest, "part")?.parse().ok()?;
+    let answer = parse_nullable_string(take_field(&mut rest, "answer")?)?;
+    let duration = Duration::from_nanos(take_field(&mut rest, "duration_ns")?.parse().ok()?);
+    let status = parse_json_string(take_field(&mut rest, "status")?)?;
+    let error = parse_nullable_string(take_field(&mut rest, "error")?)?;
+
+    Some(Record {
+        part,
+        answer,
+        duration,
+        status,
+        error,
+    })
+}
+
+/// Returns the raw value of the next field with the given key, skipping everything before it.
+fn take_field<'a>(rest: &mut &'a str, key: &str) -> Option<&'a str> {
+    let pattern = format!("\"{key}\":");
+    let start = rest.find(&pattern)? + pattern.len();
+    let value = &rest[start..];
+
+    let len = if value.starts_with('"') {
+        let mut is_escaped = false;
+        let end = value.char_indices().skip(1).find(|&(_, c)| {
+            let is_end = c == '"' && !is_escaped;
+            is_escaped = c == '\\' && !is_escaped;
+            is_end
+        })?;
+        end.0 + 1
+    } else {
+        value.find([',', '}'])?
+    };
+
+    *rest = &value[len..];
+    Some(&value[..len])
+}
+
+/// Parses a string value, `null` is parsed as `Some(None)`.
+fn parse_nullable_string(value: &str) -> Option<Option<String>> {
+    match value {
+        "null" => Some(None),
+        value => parse_json_string(value).map(Some),
+    }
+}
+
+fn parse_json_string(value: &str) -> Option<String> {
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => out.push('\n'),
+            'r' => out.push('\r'),
+            't' => out.push('\t'),
+            'u' => {
+                let code: String = chars.by_ref().take(4).collect();
+                out.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+            }
+            c => out.push(c),
+        }
+    }
+
+    Some(out)
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{json_string, parse_record, to_json, to_json_array, Record};
+    use crate::puzzle;
+    use crate::template::memory::MemoryStats;
+    use crate::template::runner::PartResult;
+    use crate::template::stats::Stats;
+    use crate::template::SolveError;
+
+    fn result(answer: Option<&str>) -> PartResult {
+        PartResult {
+            puzzle: puzzle!(2023, 5),
+            part: 2,
+            answer: answer.map(Into::into),
+            duration: Duration::from_nanos(1500),
+            samples: 10,
+            stats: None,
+            verification: None,
+            error: None,
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+    }
+
+    #[test]
+    fn serializes_record() {
+        assert_eq!(
+            to_json(&result(Some("42"))),
+            r#"{"year":2023,"day":5,"part":2,"answer":"42","duration_ns":1500,"samples":10,"status":"solved","stats":null,"memory":null,"error":null}"#
+        );
+    }
+
+    #[test]
+    fn serializes_unsolved_record() {
+        assert_eq!(
+            to_json(&result(None)),
+            r#"{"year":2023,"day":5,"part":2,"answer":null,"duration_ns":1500,"samples":10,"status":"unsolved","stats":null,"memory":null,"error":null}"#
+        );
+    }
+
+    #[test]
+    fn serializes_failed_record() {
+        let record = PartResult {
+            error: Some(SolveError::Invalid("no start".into())),
+            ..result(None)
+        };
+        assert!(to_json(&record).ends_with(
+            r#""status":"error","stats":null,"memory":null,"error":"invalid input: no start"}"#
+        ));
+    }
+
+    #[test]
+    fn serializes_memory() {
+        let record = PartResult {
+            memory: Some(MemoryStats {
+                peak_bytes: 2048,
+                total_bytes: 4096,
+                allocations: 3,
+            }),
+            ..result(Some("42"))
+        };
+        assert!(to_json(&record).ends_with(
+            r#""memory":{"peak_bytes":2048,"total_bytes":4096,"allocations":3},"error":null}"#
+        ));
+    }
+
+    #[test]
+    fn parses_records() {
+        let record = PartResult {
+            answer: Some("\"part\":3,\n\u{1}\\".into()),
+            error: Some(SolveError::Invalid("\"error\":null".into())),
+            ..result(None)
+        };
+
+        assert_eq!(
+            parse_record(&to_json(&record)),
+            Some(Record {
+                part: 2,
+                answer: Some("\"part\":3,\n\u{1}\\".into()),
+                duration: Duration::from_nanos(1500),
+                status: "error".into(),
+                error: Some("invalid input: \"error\":null".into()),
+            })
+        );
+        assert_eq!(
+            parse_record(&to_json(&result(None))).map(|record| record.answer),
+            Some(None)
+        );
+        assert_eq!(parse_record("Part 1: 42"), None);
+    }
+
+    #[test]
+    fn serializes_array() {
+        assert_eq!(to_json_array(&[]), "[]");
+        assert_eq!(
+            to_json_array(&[result(None), result(None)])
+                .matches("\"day\":5")
+                .count(),
+            2
+        );
+    }
+}
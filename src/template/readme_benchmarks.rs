@@ -2,7 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::get_bin_path;
+use crate::template::memory::{format_bytes, MemoryStats};
+use crate::template::stats::Stats;
+use crate::PuzzleId;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -20,9 +23,16 @@ impl From<std::io::Error> for Error {
 
 #[derive(Clone)]
 pub struct Timings {
-    pub day: Day,
+    pub puzzle: PuzzleId,
+    /// The timing of the `parse` hook, if the solution has one.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
     pub total_nanos: f64,
 }
 
@@ -31,11 +41,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -58,26 +63,63 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
+/// Formats memory usage as `peak / total` for a table cell.
+fn format_memory(memory: Option<MemoryStats>) -> String {
+    memory.map_or_else(
+        || "-".into(),
+        |m| {
+            format!(
+                "{} / {}",
+                format_bytes(m.peak_bytes),
+                format_bytes(m.total_bytes)
+            )
+        },
+    )
+}
+
 fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
+    let has_parse = timings.iter().any(|t| t.parse.is_some());
+    let has_memory = timings
+        .iter()
+        .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some());
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_memory {
+        columns.extend(["Part 1 memory", "Part 2 memory"]);
+    }
 
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", columns.join(" | ")),
+        format!("| {}  |", vec![":---:"; columns.len()].join(" | ")),
     ];
 
+    let or_dash = |timing: Option<String>| timing.unwrap_or_else(|| "-".into());
+
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let path = format!("./{}", get_bin_path(timing.puzzle).display());
+        let mut cells = vec![format!(
+            "[Day {}]({})",
+            timing.puzzle.day.into_inner(),
+            path
+        )];
+        if has_parse {
+            cells.push(format!("`{}`", or_dash(timing.parse)));
+        }
+        cells.push(format!("`{}`", or_dash(timing.part_1)));
+        cells.push(format!("`{}`", or_dash(timing.part_2)));
+        if has_memory {
+            cells.push(format!("`{}`", format_memory(timing.part_1_memory)));
+            cells.push(format!("`{}`", format_memory(timing.part_2_memory)));
+        }
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
@@ -105,26 +147,45 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use crate::puzzle;
+    use crate::template::memory::MemoryStats;
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
-                day: day!(1),
+                puzzle: puzzle!(2023, 1),
+                parse: None,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 3e+10,
             },
             Timings {
-                day: day!(2),
+                puzzle: puzzle!(2023, 2),
+                parse: None,
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 7e+10,
             },
             Timings {
-                day: day!(4),
+                puzzle: puzzle!(2023, 4),
+                parse: None,
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
+                parse_stats: None,
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -172,9 +233,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +244,34 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings[0].part_1_memory = Some(MemoryStats {
+            peak_bytes: 1536,
+            total_bytes: 4096,
+            allocations: 3,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2023-01.rs) | `10ms` | `20ms` | `1.5 KiB / 4.0 KiB` | `-` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/2023-02.rs) | `30ms` | `40ms` | `-` | `-` |"));
+    }
+
+    #[test]
+    fn format_parse_column() {
+        let mut timings = get_mock_timings();
+        timings[1].parse = Some("5ms".into());
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |\n| :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2023-02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
 }
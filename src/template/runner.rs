@@ -1,94 +1,762 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::answers::{self, Answers, Verification};
+use crate::template::aoc_client::AocClient;
+use crate::template::baseline::{self, Baseline};
+use crate::template::input::InputSource;
+use crate::template::memory::{self, MemoryStats};
+use crate::template::output::{self, OutputFormat};
+use crate::template::stats::Stats;
+use crate::template::submissions::{SubmissionLog, SubmissionOutcome};
+use crate::template::{Answer, Solution, SolveError, ANSI_ITALIC, ANSI_RESET};
+use crate::{PuzzleId, Year};
+use std::any::Any;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Options that control how a solution is run.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Bench each part instead of executing it once.
+    pub time: bool,
+    /// Submit the result of this part to the Advent of Code website.
+    pub submit: Option<u8>,
+    /// The format results are printed in.
+    pub format: OutputFormat,
+    /// Compare benched timings against this baseline.
+    pub baseline: Option<String>,
+    /// Save benched timings to this baseline.
+    pub save_baseline: Option<String>,
+    /// Check answers against the known answers in `data/<year>/answers`.
+    pub verify: bool,
+    /// Abandon a part if it does not return within this duration.
+    pub timeout: Option<Duration>,
+    /// Don't print results as parts finish, e.g. because days run concurrently and are printed
+    /// once they are done. Not a command-line option.
+    pub silent: bool,
+    /// The input to run against instead of the puzzle input.
+    pub input: InputSource,
+    /// Only run this part, instead of both.
+    pub part: Option<u8>,
+    /// Count the heap allocations of each part.
+    pub mem: bool,
+}
+
+impl RunOptions {
+    /// Parses the options shared by `solve`, `all` and the solution binaries.
+    /// `--submit` is only valid for a single day and is parsed by the caller, before these options.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        // `--example` takes an optional value, so the input is parsed once everything else is.
+        let mut options = Self {
+            time: args.contains("--time"),
+            submit: None,
+            format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+            baseline: args.opt_value_from_fn("--baseline", baseline::parse_name)?,
+            save_baseline: args.opt_value_from_fn("--save-baseline", baseline::parse_name)?,
+            verify: args.contains("--verify"),
+            timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
+            silent: false,
+            input: InputSource::default(),
+            part: args.opt_value_from_fn("--part", parse_part)?,
+            mem: args.contains("--mem"),
+        };
+
+        options.input = InputSource::parse(args)?;
+        Ok(options)
+    }
+
+    /// Reads the options passed to a solution binary, e.g. `cargo solve 1 --time --submit 1 --format json`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let mut args = pico_args::Arguments::from_env();
+
+        let options = args.opt_value_from_str("--submit").and_then(|submit| {
+            Ok(Self {
+                submit,
+                ..Self::parse(&mut args)?
+            })
+        });
+
+        match options {
+            Ok(options) => options,
+            Err(e) => {
+                eprintln!("Unexpected command-line input: {e}.");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Converts the options back into the arguments of a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(submit) = self.submit {
+            args.push("--submit".into());
+            args.push(submit.to_string());
+        }
+
+        if self.time {
+            args.push("--time".into());
+        }
+
+        if !self.format.is_text() {
+            args.push("--format".into());
+            args.push(self.format.to_string());
+        }
+
+        if let Some(baseline) = &self.baseline {
+            args.push("--baseline".into());
+            args.push(baseline.clone());
+        }
+
+        if let Some(baseline) = &self.save_baseline {
+            args.push("--save-baseline".into());
+            args.push(baseline.clone());
+        }
+
+        if self.verify {
+            args.push("--verify".into());
+        }
+
+        if let Some(timeout) = self.timeout {
+            args.push("--timeout".into());
+            args.push(timeout.as_secs_f64().to_string());
+        }
+
+        if let Some(part) = self.part {
+            args.push("--part".into());
+            args.push(part.to_string());
+        }
+
+        if self.mem {
+            args.push("--mem".into());
+        }
+
+        args.extend(self.input.to_args());
+
+        args
+    }
+
+    /// Whether the given part is run, i.e. not excluded by `--part`.
+    #[must_use]
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|only| only == part)
+    }
+}
+
+fn parse_part(s: &str) -> Result<u8, &'static str> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err("expecting part `1` or `2`"),
+    }
+}
+
+/// Parses a timeout in seconds, e.g. `10` or `0.5`.
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    s.parse()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| format!("invalid timeout `{s}`, expecting a number of seconds"))
+}
+
+/// The part number the `parse` hook of a solution is reported with.
+pub const PARSE_PART: u8 = 0;
+
+/// The name of a part in the output, e.g. `Part 1`, or `Parse` for the `parse` hook.
+#[must_use]
+pub fn part_label(part: u8) -> String {
+    if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// The outcome of running one part of a solution.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+    /// Sample statistics, present if the part was benched.
+    pub stats: Option<Stats>,
+    /// The check against the known answer, present if running with `--verify`.
+    pub verification: Option<Verification>,
+    /// The error the part failed with, if any.
+    pub error: Option<SolveError>,
+    /// Heap usage of the first execution, present if running with `--mem`.
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartResult {
+    /// Whether this part was benched, as opposed to executed once.
+    #[must_use]
+    pub fn is_benched(&self) -> bool {
+        self.samples > 1
+    }
+
+    /// A short description of the outcome, as used in machine-readable output.
+    #[must_use]
+    pub fn status(&self) -> &'static str {
+        match &self.error {
+            Some(SolveError::Panicked(_)) => return "panicked",
+            Some(SolveError::TimedOut(_)) => return "timeout",
+            Some(_) => return "error",
+            None if self.part == PARSE_PART => return "parsed",
+            None => {}
+        }
+
+        match (&self.verification, &self.answer) {
+            (Some(Verification::Correct), _) => "correct",
+            (Some(Verification::Incorrect { .. }), _) => "incorrect",
+            (_, Some(_)) => "solved",
+            (_, None) => "unsolved",
+        }
+    }
+
+    #[must_use]
+    pub fn is_incorrect(&self) -> bool {
+        self.verification
+            .as_ref()
+            .is_some_and(Verification::is_incorrect)
+    }
+
+    /// Whether the part failed with an error or returned an incorrect answer.
+    #[must_use]
+    pub fn is_failed(&self) -> bool {
+        self.error.is_some() || self.is_incorrect()
+    }
+
+    /// The measured duration, formatted like it is printed by the runner.
+    #[must_use]
+    pub fn duration_str(&self) -> String {
+        format!("{:.1?}", self.duration)
+    }
+}
+
+/// Entry point of the `main` function generated by [`solution!`](crate::solution).
+pub fn run_main(solution: &Solution) {
+    let options = RunOptions::from_env();
+    let year = solution.puzzle.year;
+
+    if let Some(submit) = options.submit.filter(|part| !options.runs_part(*part)) {
+        eprintln!("Can't submit part {submit}, as it is not run with `--part`.");
+        process::exit(1);
+    }
+
+    let [one, two] = options.input.read_or_exit(solution.puzzle);
+    let results = (solution.run)([&one, &two], &options);
+
+    if let Some(baseline) = load_baseline(year, &options) {
+        print_comparisons(&baseline, &results, &options);
+    }
+
+    output::print_records(&results, options.format);
+    save_baseline(year, &results, &options);
+
+    if results.iter().any(PartResult::is_failed) {
+        process::exit(1);
+    }
+}
+
+/// The return types supported for solution parts.
+pub trait PartOutput {
+    /// Converts the output into an answer, or `None` if the part has not been solved yet.
+    fn into_answer(self) -> Result<Option<Answer>, SolveError>;
+}
+
+impl<T: Into<Answer>> PartOutput for Option<T> {
+    fn into_answer(self) -> Result<Option<Answer>, SolveError> {
+        Ok(self.map(Into::into))
+    }
+}
+
+impl<T: Into<Answer>> PartOutput for Result<T, SolveError> {
+    fn into_answer(self) -> Result<Option<Answer>, SolveError> {
+        match self {
+            Ok(answer) => Ok(Some(answer.into())),
+            Err(SolveError::Unsolved) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Loads the baseline passed via `--baseline`, if any, printing an error if it can't be read.
+#[must_use]
+pub fn load_baseline(year: Year, options: &RunOptions) -> Option<Baseline> {
+    let name = options.baseline.as_deref()?;
+
+    if !options.time {
+        eprintln!("Baselines require benched timings, pass `--time` to compare against one.");
+        return None;
+    }
+
+    match Baseline::load(year, name) {
+        Ok(baseline) => Some(baseline),
+        Err(e) => {
+            eprintln!("Failed to load baseline \"{name}\": {e}");
+            None
+        }
+    }
+}
+
+/// Prints how benched results compare against a baseline.
+pub fn print_comparisons(baseline: &Baseline, results: &[PartResult], options: &RunOptions) {
+    let name = options.baseline.as_deref().unwrap_or_default();
+
+    for result in results {
+        let Some(comparison) = baseline.compare(result) else {
+            continue;
+        };
+        let line = format!("{} vs. \"{name}\": {comparison}", part_label(result.part));
+        // keep machine-readable output parseable.
+        if options.format.is_text() {
+            println!("{line}");
+        } else {
+            eprintln!("{line}");
+        }
+    }
+}
+
+/// Records benched results into the baseline passed via `--save-baseline`, if any.
+pub fn save_baseline(year: Year, results: &[PartResult], options: &RunOptions) {
+    let Some(name) = &options.save_baseline else {
+        return;
+    };
+
+    if !options.time {
+        eprintln!("Baselines require benched timings, pass `--time` to save one.");
+        return;
+    }
+
+    let saved = Baseline::load(year, name).and_then(|mut baseline| {
+        results.iter().for_each(|result| baseline.record(result));
+        baseline.save(year, name)
+    });
+
+    match saved {
+        Ok(()) if options.format.is_text() => println!(
+            "Saved baseline \"{name}\" to \"{}\".",
+            baseline::get_path(year, name).display()
+        ),
+        Ok(()) => {}
+        Err(e) => eprintln!("Failed to save baseline \"{name}\": {e}"),
+    }
+}
+
+/// Runs one part of a solution on a worker thread, so a part that panics or exceeds `--timeout`
+/// is reported as failed instead of taking down the remaining parts and days.
+pub fn run_part<I, T>(
+    func: impl Fn(&I) -> T + Send + 'static,
+    input: Arc<I>,
+    puzzle: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+) -> PartResult
+where
+    I: ?Sized + Send + Sync + 'static,
+    T: PartOutput,
+{
     let part_str = format!("Part {part}");
+    let is_text = options.format.is_text() && !options.silent;
+    let time = options.time;
+
+    let func = move |input: &I| func(input).into_answer();
+
+    // failing parts are not benched, the error would only be reported once anyway.
+    let is_timed = move |result: &Result<_, _>| time && result.is_ok();
+
+    let (result, measurement) = run_timed(
+        func,
+        input,
+        is_timed,
+        is_text,
+        options.timeout,
+        options.mem,
+        |result| {
+            if is_text {
+                print_result(
+                    &result.as_ref().ok().and_then(Option::as_ref),
+                    &part_str,
+                    "",
+                );
+            }
+        },
+    );
+
+    let (result, error) = match result.and_then(|result| result) {
+        Ok(result) => (result, None),
+        Err(e) => (None, Some(e)),
+    };
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let answer = result.as_ref().map(ToString::to_string);
+
+    let verification = options
+        .verify
+        .then(|| verify_answer(puzzle, part, answer.as_deref()));
+
+    let part_result = PartResult {
+        puzzle,
+        part,
+        answer,
+        duration: measurement.duration,
+        samples: measurement.samples,
+        stats: measurement.stats,
+        verification,
+        error,
+        memory: measurement.memory,
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if !options.silent {
+        print_part_result(&part_result, options.format);
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(answer) = &result {
+        if options.submit == Some(part) {
+            submit_result(answer, puzzle, part);
+        }
     }
+
+    part_result
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+/// Runs the `parse` hook of a solution like a part, returning the parsed input if it succeeded.
+pub fn run_parse<P>(
+    parse: impl Fn(&str) -> Result<P, SolveError> + Send + 'static,
+    input: Arc<str>,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> (PartResult, Option<Arc<P>>)
+where
+    P: Send + Sync + 'static,
+{
+    let label = part_label(PARSE_PART);
+    let is_text = options.format.is_text() && !options.silent;
+    let time = options.time;
 
-    hook(&result);
+    let is_timed = move |result: &Result<_, _>| time && result.is_ok();
+
+    let (result, measurement) = run_timed(
+        parse,
+        input,
+        is_timed,
+        is_text,
+        options.timeout,
+        options.mem,
+        |result| {
+            if is_text {
+                print_result(&result.as_ref().ok().map(|_| "✔"), &label, "");
+            }
+        },
+    );
+
+    let (parsed, error) = match result.and_then(|result| result) {
+        Ok(parsed) => (Some(Arc::new(parsed)), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    let part_result = PartResult {
+        puzzle,
+        part: PARSE_PART,
+        answer: None,
+        duration: measurement.duration,
+        samples: measurement.samples,
+        stats: measurement.stats,
+        verification: None,
+        error,
+        memory: measurement.memory,
+    };
+
+    if !options.silent {
+        print_part_result(&part_result, options.format);
+    }
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    (part_result, parsed)
+}
+
+/// Runs a solution with a `parse` hook: the input is parsed once and both parts run against the
+/// parsed input. If the parts run against different inputs, e.g. their own examples, each input
+/// is parsed. Parts whose input could not be parsed are skipped.
+pub fn run_parsed<P, T1, T2>(
+    parse: impl Fn(&str) -> Result<P, SolveError> + Clone + Send + 'static,
+    part_one: impl Fn(&P) -> T1 + Send + 'static,
+    part_two: impl Fn(&P) -> T2 + Send + 'static,
+    [one, two]: [&str; 2],
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> Vec<PartResult>
+where
+    P: Send + Sync + 'static,
+    T1: PartOutput,
+    T2: PartOutput,
+{
+    let mut results = vec![];
+
+    let mut parse_input = |input: &str| {
+        let (result, parsed) = run_parse(parse.clone(), input.into(), puzzle, options);
+        results.push(result);
+        parsed
+    };
+
+    let [parsed_one, parsed_two] = if one == two || options.part.is_some() {
+        let parsed = parse_input(if options.runs_part(1) { one } else { two });
+        [parsed.clone(), parsed]
     } else {
-        (base_time, 1)
+        [parse_input(one), parse_input(two)]
     };
 
-    (result, run.0, run.1)
+    if let Some(input) = parsed_one.filter(|_| options.runs_part(1)) {
+        results.push(run_part(part_one, input, puzzle, 1, options));
+    }
+    if let Some(input) = parsed_two.filter(|_| options.runs_part(2)) {
+        results.push(run_part(part_two, input, puzzle, 2, options));
+    }
+
+    results
+}
+
+/// Prints the outcome of a part, replacing the progress output of the runner in text mode.
+pub fn print_part_result(result: &PartResult, format: OutputFormat) {
+    if format.is_text() {
+        let mut suffix = format_duration(&result.duration, result.samples, result.stats.as_ref());
+        if let Some(memory) = &result.memory {
+            suffix.push_str(&format!(" [{memory}]"));
+        }
+        if let Some(verification) = &result.verification {
+            suffix.push_str(&format!(" {verification}"));
+        }
+        let label = part_label(result.part);
+        if result.part == PARSE_PART {
+            print_result(&result.error.is_none().then_some("✔"), &label, &suffix);
+        } else {
+            print_result(&result.answer, &label, &suffix);
+        }
+        if let Some(error) = &result.error {
+            println!("{error}");
+        }
+    }
+
+    output::print_record(result, format);
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn verify_answer(puzzle: PuzzleId, part: u8, answer: Option<&str>) -> Verification {
+    match Answers::load(puzzle) {
+        Ok(answers) => answers.verify(part, answer),
+        Err(e) => {
+            eprintln!("Failed to load answers for {puzzle}: {e}");
+            Verification::Unknown
+        }
+    }
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// What was measured while running a part.
+#[derive(Debug)]
+struct Measurement {
+    duration: Duration,
+    samples: u128,
+    stats: Option<Stats>,
+    memory: Option<MemoryStats>,
+}
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
+impl Measurement {
+    fn once(duration: Duration, memory: Option<MemoryStats>) -> Self {
+        Self {
+            duration,
+            samples: 1,
+            stats: None,
+            memory,
+        }
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether we are running with `--time`:
+///  1. without it, the function is executed once.
+///  2. with it, the function is warmed up and then benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// The part runs on its own thread. Panics are caught and the first execution is abandoned once it
+/// exceeds the timeout; its thread can't be stopped and keeps running until the process exits.
+/// Benching stops at the same deadline, the first execution is reported if it runs out of time.
+/// With `track_memory`, the allocations of the first execution are counted.
+fn run_timed<I, T>(
+    func: impl Fn(&I) -> T + Send + 'static,
+    input: Arc<I>,
+    is_timed: impl Fn(&T) -> bool + Send + 'static,
+    show_progress: bool,
+    timeout: Option<Duration>,
+    track_memory: bool,
+    hook: impl Fn(&T),
+) -> (Result<T, SolveError>, Measurement)
+where
+    I: ?Sized + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    let (result_sender, result_receiver) = mpsc::channel();
+    let (bench_sender, bench_receiver) = mpsc::channel();
+    let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+
+    thread::spawn(move || {
+        let run = || panic::catch_unwind(AssertUnwindSafe(|| func(&input)));
+
+        let timer = Instant::now();
+        let (result, memory) = if track_memory {
+            let (result, memory) = memory::measure(run);
+            (result, Some(memory))
+        } else {
+            (run(), None)
+        };
+        let base_time = timer.elapsed();
+        let is_timed = result.as_ref().is_ok_and(&is_timed);
+
+        // the receiver is gone if the part timed out.
+        if result_sender
+            .send((result, base_time, memory, is_timed))
+            .is_err()
+            || !is_timed
+        {
+            return;
+        }
+
+        let benched = panic::catch_unwind(AssertUnwindSafe(|| {
+            bench(&func, &input, &base_time, deadline)
+        }));
+        let _ = bench_sender.send(benched);
+    });
+
+    // `recv_timeout` blocks indefinitely if the deadline overflows.
+    let (result, base_time, memory, is_timed) =
+        match result_receiver.recv_timeout(timeout.unwrap_or(Duration::MAX)) {
+            Ok((Ok(result), base_time, memory, is_timed)) => (result, base_time, memory, is_timed),
+            Ok((Err(payload), base_time, memory, _)) => {
+                return (
+                    Err(panicked(&*payload)),
+                    Measurement::once(base_time, memory),
+                );
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let timeout = timeout.unwrap_or_default();
+                return (
+                    Err(SolveError::TimedOut(timeout)),
+                    Measurement::once(timeout, None),
+                );
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                let error = SolveError::Panicked("the part exited unexpectedly".into());
+                return (Err(error), Measurement::once(Duration::ZERO, None));
+            }
+        };
+
+    hook(&result);
+
+    if !is_timed {
+        return (Ok(result), Measurement::once(base_time, memory));
+    }
+
+    if show_progress {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+
+    let remaining = deadline.map_or(Duration::MAX, |deadline| {
+        deadline.saturating_duration_since(Instant::now())
+    });
+
+    match bench_receiver.recv_timeout(remaining) {
+        Ok(Ok(Some((stats, samples)))) => (
+            Ok(result),
+            Measurement {
+                duration: stats.mean,
+                samples,
+                stats: Some(stats),
+                memory,
+            },
         ),
-    );
+        Ok(Err(payload)) => (
+            Err(panicked(&*payload)),
+            Measurement::once(base_time, memory),
+        ),
+        // out of time, fall back to the first execution.
+        Ok(Ok(None)) | Err(RecvTimeoutError::Timeout) => {
+            (Ok(result), Measurement::once(base_time, memory))
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            let error = SolveError::Panicked("the part exited unexpectedly".into());
+            (Err(error), Measurement::once(base_time, memory))
+        }
+    }
+}
+
+/// Converts the payload of a caught panic into an error, keeping the panic message.
+fn panicked(payload: &(dyn Any + Send)) -> SolveError {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown cause".into());
+
+    SolveError::Panicked(message)
+}
+
+/// Benches a part, returns [`None`] if the deadline passes before all samples are taken.
+fn bench<I: ?Sized, T>(
+    func: impl Fn(&I) -> T,
+    input: &I,
+    base_time: &Duration,
+    deadline: Option<Instant>,
+) -> Option<(Stats, u128)> {
+    let out_of_time = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / base_time.as_nanos().max(10)).clamp(10, 10000);
+
+    // warm up caches and branch predictors before taking any measurements.
+    for _ in 0..(bench_iterations / 10) {
+        if out_of_time() {
+            return None;
+        }
+        func(input);
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
+        if out_of_time() {
+            return None;
+        }
         let timer = Instant::now();
-        func(cloned);
+        func(input);
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // the iteration count is clamped to at least 10, so there always are samples.
+    Some((Stats::from_samples(&timers).unwrap(), bench_iterations))
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
-
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_duration(duration: &Duration, samples: u128, stats: Option<&Stats>) -> String {
+    match stats {
+        Some(stats) if samples > 1 => format!(
+            " ({duration:.1?} @ {samples} samples, median {:.1?} ± {:.1?}, p5 {:.1?}, p95 {:.1?}, min {:.1?}, max {:.1?}, {} outliers)",
+            stats.median,
+            stats.std_dev,
+            stats.p5,
+            stats.p95,
+            stats.min,
+            stats.max,
+            stats.outliers.total(),
+        ),
+        _ if samples > 1 => format!(" ({duration:.1?} @ {samples} samples)"),
+        _ => format!(" ({duration:.1?})"),
     }
 }
 
@@ -127,41 +795,150 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Submit one part of the solution, unless the submission log shows that the answer is wrong.
+/// Correct answers are added to the answer store.
+fn submit_result(answer: &Answer, puzzle: PuzzleId, part: u8) {
+    let result = &answer.to_string();
 
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
+    let mut log = match SubmissionLog::load(puzzle) {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("Failed to load submissions: {e}");
+            process::exit(1);
+        }
+    };
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    if let Err(refusal) = log.check(part, result) {
+        eprintln!("Not submitting \"{result}\": {refusal}.");
         process::exit(1);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    println!("Submitting result...");
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let (outcome, message) = match AocClient::from_env().submit(puzzle, part, answer) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            process::exit(1);
+        }
     };
 
-    if part_submit != part {
-        return None;
+    println!("{message}");
+
+    log.record(part, result, outcome);
+    if let Err(e) = log.save(puzzle) {
+        eprintln!("Failed to save submission: {e}");
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if outcome == SubmissionOutcome::Correct {
+        match answers::record(puzzle, part, result) {
+            Ok(()) => println!(
+                "Saved answer to \"{}\".",
+                answers::get_path(puzzle).display()
+            ),
+            Err(e) => eprintln!("Failed to save answer: {e}"),
+        }
     }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    use super::{parse_timeout, run_timed};
+    use crate::template::SolveError;
+
+    #[test]
+    fn parses_timeouts() {
+        assert_eq!(parse_timeout("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_timeout("0.5"), Ok(Duration::from_millis(500)));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("-1").is_err());
+        assert!(parse_timeout("10s").is_err());
+    }
+
+    #[test]
+    fn runs_parts() {
+        let (result, measurement) = run_timed(
+            |input: &str| input.len(),
+            Arc::from("abc"),
+            |_| false,
+            false,
+            None,
+            false,
+            |_| {},
+        );
+        assert_eq!(result, Ok(3));
+        assert_eq!(measurement.samples, 1);
+        assert!(measurement.stats.is_none());
+        assert!(measurement.memory.is_none());
+    }
+
+    #[test]
+    fn measures_memory() {
+        let (result, measurement) = run_timed(
+            |input: &str| input.repeat(1000).len(),
+            Arc::from("abc"),
+            |_| false,
+            false,
+            None,
+            true,
+            |_| {},
+        );
+        assert_eq!(result, Ok(3000));
+        assert!(measurement
+            .memory
+            .is_some_and(|memory| memory.total_bytes >= 3000));
+    }
+
+    #[test]
+    fn catches_panics() {
+        let (result, ..) = run_timed(
+            |input: &str| -> usize { panic!("bad input: {input}") },
+            Arc::from("abc"),
+            |_| true,
+            false,
+            None,
+            false,
+            |_| {},
+        );
+        assert_eq!(result, Err(SolveError::Panicked("bad input: abc".into())));
+    }
+
+    #[test]
+    fn abandons_parts_after_timeout() {
+        let timeout = Duration::from_millis(10);
+        let (result, measurement) = run_timed(
+            |_: &str| thread::sleep(Duration::from_secs(1)),
+            Arc::from(""),
+            |_| false,
+            false,
+            Some(timeout),
+            false,
+            |_| {},
+        );
+        assert_eq!(result, Err(SolveError::TimedOut(timeout)));
+        assert_eq!(measurement.duration, timeout);
+    }
+
+    #[test]
+    fn stops_benching_after_timeout() {
+        // the first run fits into the timeout, but benching needs at least 11 more runs.
+        let timer = Instant::now();
+        let (result, measurement) = run_timed(
+            |_: &str| thread::sleep(Duration::from_millis(20)),
+            Arc::from(""),
+            |_| true,
+            false,
+            Some(Duration::from_millis(100)),
+            false,
+            |_| {},
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(measurement.samples, 1);
+        assert!(measurement.stats.is_none());
+        assert!(timer.elapsed() < Duration::from_millis(200));
+    }
 }
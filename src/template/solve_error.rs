@@ -0,0 +1,192 @@
+/// Module that defines the error a solution part can fail with.
+///
+/// Parse errors are located in the input with `nom_locate`, so a malformed input is reported
+/// with its line, column and a caret pointing at the offending character.
+use std::fmt::Display;
+use std::time::Duration;
+
+use nom::error::{Error as NomError, ErrorKind};
+use nom::{IResult, Offset, Slice};
+use nom_locate::LocatedSpan;
+
+/// Input type for parsers that want to track their location in the input.
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The part has not been solved yet.
+    Unsolved,
+    /// The input could not be parsed.
+    Parse {
+        line: u32,
+        column: usize,
+        /// The line of the input the error occurred in.
+        source_line: String,
+        /// What the failing parser expected.
+        expected: String,
+    },
+    /// The input was parsed, but does not describe a solvable puzzle.
+    Invalid(String),
+    /// The part panicked, the message of the panic is kept.
+    Panicked(String),
+    /// The part did not return within `--timeout` and was abandoned.
+    TimedOut(Duration),
+}
+
+impl SolveError {
+    fn located(span: Span, kind: ErrorKind) -> Self {
+        Self::Parse {
+            line: span.location_line(),
+            column: span.get_utf8_column(),
+            source_line: String::from_utf8_lossy(span.get_line_beginning()).into_owned(),
+            expected: match kind {
+                ErrorKind::Eof => "end of input".into(),
+                kind => kind.description().to_lowercase(),
+            },
+        }
+    }
+
+    /// Locates the error of a parser over a plain `&str` in the input it was run on.
+    #[must_use]
+    pub fn from_nom(input: &str, e: nom::Err<NomError<&str>>) -> Self {
+        match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                Self::located(Span::new(input).slice(input.offset(e.input)..), e.code)
+            }
+            nom::Err::Incomplete(_) => Self::Invalid("the input ended unexpectedly".into()),
+        }
+    }
+}
+
+impl From<nom::Err<NomError<Span<'_>>>> for SolveError {
+    fn from(e: nom::Err<NomError<Span<'_>>>) -> Self {
+        match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => Self::located(e.input, e.code),
+            nom::Err::Incomplete(_) => Self::Invalid("the input ended unexpectedly".into()),
+        }
+    }
+}
+
+impl From<&str> for SolveError {
+    fn from(message: &str) -> Self {
+        Self::Invalid(message.into())
+    }
+}
+
+impl From<String> for SolveError {
+    fn from(message: String) -> Self {
+        Self::Invalid(message)
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsolved => write!(f, "not solved yet"),
+            Self::Parse {
+                line,
+                column,
+                source_line,
+                expected,
+            } => {
+                let gutter = " ".repeat(line.to_string().len());
+                writeln!(
+                    f,
+                    "parse error at line {line}, column {column}: expected {expected}"
+                )?;
+                writeln!(f, "{gutter} |")?;
+                writeln!(f, "{line} | {source_line}")?;
+                write!(f, "{gutter} | {}^", " ".repeat(column.saturating_sub(1)))
+            }
+            Self::Invalid(message) => write!(f, "invalid input: {message}"),
+            Self::Panicked(message) => write!(f, "panicked: {message}"),
+            Self::TimedOut(timeout) => write!(f, "timed out after {timeout:?}"),
+        }
+    }
+}
+
+/// Runs a parser over the whole input, turning a failure into a located [`SolveError`].
+///
+/// Only trailing whitespace may be left unparsed, so a parser that stops early at a malformed
+/// line reports that line instead of silently dropping the rest of the input.
+pub fn parse_input<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, SolveError> {
+    let (rest, output) = parser(input).map_err(|e| SolveError::from_nom(input, e))?;
+
+    let rest = rest.trim_start();
+    if rest.is_empty() {
+        Ok(output)
+    } else {
+        let e = NomError::new(rest, ErrorKind::Eof);
+        Err(SolveError::from_nom(input, nom::Err::Error(e)))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{newline, u32};
+    use nom::multi::separated_list1;
+    use nom::sequence::preceded;
+    use nom::IResult;
+
+    use super::{parse_input, SolveError, Span};
+
+    fn numbers(i: &str) -> IResult<&str, Vec<u32>> {
+        separated_list1(newline, preceded(tag("n="), u32))(i)
+    }
+
+    #[test]
+    fn parses_valid_input() {
+        assert_eq!(parse_input("n=1\nn=2\n", numbers), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn rejects_unparsed_input() {
+        let error = parse_input("n=1\nn=2\n x=3\n", numbers).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 3, column 2: expected end of input\n  |\n3 |  x=3\n  |  ^"
+        );
+    }
+
+    #[test]
+    fn locates_parse_errors() {
+        let error = parse_input("n=1", |i| preceded(newline, numbers)(i)).unwrap_err();
+        assert_eq!(
+            error,
+            SolveError::Parse {
+                line: 1,
+                column: 1,
+                source_line: "n=1".into(),
+                expected: "char".into(),
+            }
+        );
+
+        let error = parse_input("n=1\nn=x2\n", |i| {
+            let (i, _) = numbers(i)?;
+            preceded(tag("\nn="), u32)(i)
+        })
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 2, column 3: expected digit\n  |\n2 | n=x2\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn locates_span_errors() {
+        let result: IResult<Span, Span> = tag("b")(Span::new("a\nxb"));
+        let error: SolveError = result.unwrap_err().into();
+        assert!(matches!(
+            error,
+            SolveError::Parse {
+                line: 1,
+                column: 1,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,156 @@
+/// Summary statistics over benchmark samples.
+use std::time::Duration;
+
+/// Statistics of a benched solution part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub std_dev: Duration,
+    pub p5: Duration,
+    pub p95: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub outliers: Outliers,
+}
+
+/// Samples outside of the inner (1.5 IQR, mild) and outer (3 IQR, severe) Tukey fences.
+///
+/// This is the same classification that criterion uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Outliers {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+impl Outliers {
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.low_severe + self.low_mild + self.high_mild + self.high_severe
+    }
+}
+
+impl Stats {
+    /// Computes statistics for a set of samples, returns [`None`] if there are none.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = if nanos.len() > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        let q1 = percentile(&nanos, 25.0);
+        let q3 = percentile(&nanos, 75.0);
+        let iqr = q3 - q1;
+
+        let mut outliers = Outliers::default();
+        for &x in &nanos {
+            if x < q1 - 3.0 * iqr {
+                outliers.low_severe += 1;
+            } else if x < q1 - 1.5 * iqr {
+                outliers.low_mild += 1;
+            } else if x > q3 + 3.0 * iqr {
+                outliers.high_severe += 1;
+            } else if x > q3 + 1.5 * iqr {
+                outliers.high_mild += 1;
+            }
+        }
+
+        Some(Self {
+            mean: from_nanos(mean),
+            median: from_nanos(percentile(&nanos, 50.0)),
+            std_dev: from_nanos(variance.sqrt()),
+            p5: from_nanos(percentile(&nanos, 5.0)),
+            p95: from_nanos(percentile(&nanos, 95.0)),
+            min: from_nanos(nanos[0]),
+            max: from_nanos(nanos[nanos.len() - 1]),
+            outliers,
+        })
+    }
+}
+
+/// Linearly interpolated percentile of sorted values.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = pct / 100.0 * (sorted.len() - 1) as f64;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.max(0.0).round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{percentile, Stats};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&n| Duration::from_nanos(n)).collect()
+    }
+
+    #[test]
+    fn empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::from_samples(&nanos(&[100])).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(100));
+        assert_eq!(stats.median, Duration::from_nanos(100));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert_eq!(stats.outliers.total(), 0);
+    }
+
+    #[test]
+    fn interpolates_percentiles() {
+        let sorted = [10.0, 20.0, 30.0, 40.0];
+        assert_eq!(percentile(&sorted, 0.0), 10.0);
+        assert_eq!(percentile(&sorted, 50.0), 25.0);
+        assert_eq!(percentile(&sorted, 100.0), 40.0);
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let stats = Stats::from_samples(&nanos(&[50, 10, 40, 20, 30])).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.p5, Duration::from_nanos(12));
+        assert_eq!(stats.p95, Duration::from_nanos(48));
+        // sample standard deviation of 10..=50 is sqrt(250).
+        assert_eq!(stats.std_dev, Duration::from_nanos(16));
+    }
+
+    #[test]
+    fn classifies_outliers() {
+        let mut samples: Vec<u64> = (1..=100).collect();
+        samples.extend([170, 1000]);
+        let stats = Stats::from_samples(&nanos(&samples)).unwrap();
+        assert_eq!(stats.outliers.high_mild, 1);
+        assert_eq!(stats.outliers.high_severe, 1);
+        assert_eq!(stats.outliers.total(), 2);
+    }
+}
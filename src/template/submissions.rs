@@ -0,0 +1,412 @@
+/// Module that classifies submission responses and keeps a local log of submitted answers.
+/// The log lets the runner refuse answers that are already known to be wrong before they reach the server.
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, io};
+
+use crate::template::get_data_path;
+use crate::PuzzleId;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse submission log: {e}"),
+            Error::IO(e) => write!(f, "could not access submission log: {e}"),
+        }
+    }
+}
+
+/// The response to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// The part has already been solved, or is not unlocked yet.
+    AlreadySolved,
+    /// The answer was not checked because the previous submission was too recent.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+}
+
+impl SubmissionOutcome {
+    /// Classifies the text of a submission response.
+    #[must_use]
+    pub fn parse(message: &str) -> Option<Self> {
+        if message.contains("That's the right answer") {
+            Some(Self::Correct)
+        } else if message.contains("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                Some(Self::TooHigh)
+            } else if message.contains("your answer is too low") {
+                Some(Self::TooLow)
+            } else {
+                Some(Self::Incorrect)
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Some(Self::RateLimited {
+                wait: parse_wait(message),
+            })
+        } else if message.contains("You don't seem to be solving the right level") {
+            Some(Self::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the answer is known to be wrong after this outcome.
+    #[must_use]
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Incorrect)
+    }
+
+    fn key(self) -> Option<&'static str> {
+        match self {
+            Self::Correct => Some("correct"),
+            Self::TooHigh => Some("too_high"),
+            Self::TooLow => Some("too_low"),
+            Self::Incorrect => Some("incorrect"),
+            // these say nothing about the answer itself.
+            Self::AlreadySolved | Self::RateLimited { .. } => None,
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "correct" => Some(Self::Correct),
+            "too_high" => Some(Self::TooHigh),
+            "too_low" => Some(Self::TooLow),
+            "incorrect" => Some(Self::Incorrect),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+            Self::Incorrect => write!(f, "incorrect"),
+            Self::AlreadySolved => write!(f, "already solved"),
+            Self::RateLimited { wait: Some(wait) } => write!(f, "rate limited for {wait:?}"),
+            Self::RateLimited { wait: None } => write!(f, "rate limited"),
+        }
+    }
+}
+
+/// Parses the wait time from a message like "You have 1m 39s left to wait.".
+fn parse_wait(message: &str) -> Option<Duration> {
+    let (before, _) = message.split_once(" left to wait")?;
+    let (_, wait) = before.rsplit_once("You have ")?;
+
+    wait.split_whitespace()
+        .map(|part| {
+            let split = part.find(|c: char| !c.is_ascii_digit())?;
+            let value: u64 = part[..split].parse().ok()?;
+            match &part[split..] {
+                "s" => Some(value),
+                "m" => Some(value * 60),
+                "h" => Some(value * 3600),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Why an answer was not submitted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    KnownWrong { outcome: SubmissionOutcome },
+    NotAbove { bound: i128 },
+    NotBelow { bound: i128 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with \"{answer}\"")
+            }
+            Refusal::KnownWrong { outcome } => {
+                write!(f, "this answer was already submitted and is {outcome}")
+            }
+            Refusal::NotAbove { bound } => {
+                write!(f, "{bound} was too low, so the answer has to be larger")
+            }
+            Refusal::NotBelow { bound } => {
+                write!(f, "{bound} was too high, so the answer has to be smaller")
+            }
+        }
+    }
+}
+
+/// A logged submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmissionOutcome,
+}
+
+/// The submissions of a day, stored in `data/<year>/submissions/NN.tsv`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    entries: Vec<Entry>,
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> PathBuf {
+    get_data_path(puzzle, "submissions", "tsv")
+}
+
+impl SubmissionLog {
+    /// Loads the log of a day, returns an empty log if nothing has been submitted yet.
+    pub fn load(puzzle: PuzzleId) -> Result<Self, Error> {
+        match fs::read_to_string(get_path(puzzle)) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        let path = get_path(puzzle);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.serialize())?;
+        Ok(())
+    }
+
+    /// Adds a submission to the log, skipping outcomes that say nothing about the answer.
+    pub fn record(&mut self, part: u8, answer: &str, outcome: SubmissionOutcome) {
+        if outcome.key().is_some() {
+            self.entries.push(Entry {
+                part,
+                answer: answer.into(),
+                outcome,
+            });
+        }
+    }
+
+    /// Checks whether an answer is worth submitting, given what is known about the part.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Refusal> {
+        let entries = self.entries.iter().filter(|e| e.part == part);
+
+        if let Some(entry) = entries
+            .clone()
+            .find(|e| e.outcome == SubmissionOutcome::Correct)
+        {
+            return Err(Refusal::AlreadySolved {
+                answer: entry.answer.clone(),
+            });
+        }
+
+        if let Some(entry) = entries.clone().find(|e| e.answer == answer) {
+            return Err(Refusal::KnownWrong {
+                outcome: entry.outcome,
+            });
+        }
+
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |outcome| {
+            entries
+                .clone()
+                .filter(move |e| e.outcome == outcome)
+                .filter_map(|e| e.answer.trim().parse::<i128>().ok())
+        };
+
+        if let Some(bound) = bound(SubmissionOutcome::TooLow).max() {
+            if value <= bound {
+                return Err(Refusal::NotAbove { bound });
+            }
+        }
+
+        if let Some(bound) = bound(SubmissionOutcome::TooHigh).min() {
+            if value >= bound {
+                return Err(Refusal::NotBelow { bound });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse(s: &str) -> Result<Self, Error> {
+        let mut log = Self::default();
+
+        for line in s.lines().filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [part, answer, outcome] = fields[..] else {
+                return Err(Error::Parser(format!("malformed line \"{line}\"")));
+            };
+
+            log.entries.push(Entry {
+                part: part
+                    .parse()
+                    .map_err(|_| Error::Parser(format!("invalid part in \"{line}\"")))?,
+                answer: unescape(answer),
+                outcome: SubmissionOutcome::from_key(outcome)
+                    .ok_or_else(|| Error::Parser(format!("invalid outcome in \"{line}\"")))?,
+            });
+        }
+
+        Ok(log)
+    }
+
+    fn serialize(&self) -> String {
+        let mut lines = vec!["# part\tanswer\toutcome".to_string()];
+        for entry in &self.entries {
+            if let Some(key) = entry.outcome.key() {
+                lines.push(format!("{}\t{}\t{key}", entry.part, escape(&entry.answer)));
+            }
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('t')) => {
+                out.push('\t');
+                chars.next();
+            }
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            (c, _) => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Refusal, SubmissionLog, SubmissionOutcome};
+
+    #[test]
+    fn classifies_responses() {
+        let parse = SubmissionOutcome::parse;
+        assert_eq!(
+            parse("That's the right answer! You are one gold star closer."),
+            Some(SubmissionOutcome::Correct)
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too high. Please wait one minute."),
+            Some(SubmissionOutcome::TooHigh)
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too low."),
+            Some(SubmissionOutcome::TooLow)
+        );
+        assert_eq!(
+            parse("That's not the right answer. If you're stuck, make sure you're using the full input data."),
+            Some(SubmissionOutcome::Incorrect)
+        );
+        assert_eq!(
+            parse("You don't seem to be solving the right level. Did you already complete it?"),
+            Some(SubmissionOutcome::AlreadySolved)
+        );
+        assert_eq!(
+            parse("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 39s left to wait."),
+            Some(SubmissionOutcome::RateLimited {
+                wait: Some(Duration::from_secs(99))
+            })
+        );
+        assert_eq!(parse("Something else entirely."), None);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let mut log = SubmissionLog::default();
+        log.record(1, "abc", SubmissionOutcome::Incorrect);
+        assert_eq!(
+            log.check(1, "abc"),
+            Err(Refusal::KnownWrong {
+                outcome: SubmissionOutcome::Incorrect
+            })
+        );
+        assert_eq!(log.check(1, "abd"), Ok(()));
+        assert_eq!(log.check(2, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let mut log = SubmissionLog::default();
+        log.record(1, "100", SubmissionOutcome::TooLow);
+        log.record(1, "150", SubmissionOutcome::TooLow);
+        log.record(1, "300", SubmissionOutcome::TooHigh);
+        assert_eq!(log.check(1, "120"), Err(Refusal::NotAbove { bound: 150 }));
+        assert_eq!(
+            log.check(1, "300"),
+            Err(Refusal::KnownWrong {
+                outcome: SubmissionOutcome::TooHigh
+            })
+        );
+        assert_eq!(log.check(1, "301"), Err(Refusal::NotBelow { bound: 300 }));
+        assert_eq!(log.check(1, "200"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut log = SubmissionLog::default();
+        log.record(2, "42", SubmissionOutcome::Correct);
+        assert_eq!(
+            log.check(2, "43"),
+            Err(Refusal::AlreadySolved {
+                answer: "42".into()
+            })
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut log = SubmissionLog::default();
+        log.record(1, "a\tb\n\\c", SubmissionOutcome::TooHigh);
+        log.record(1, "1", SubmissionOutcome::RateLimited { wait: None });
+        log.record(2, "2", SubmissionOutcome::Correct);
+        let parsed = SubmissionLog::parse(&log.serialize()).unwrap();
+        assert_eq!(parsed, log);
+        assert_eq!(parsed.entries.len(), 2);
+    }
+}